@@ -0,0 +1,44 @@
+use std::process::ExitCode;
+
+use crate::game::tilemap::validation::validate_map;
+
+/**
+ * `--check-map <tmx>...` : validates maps without starting the game, for level designers.
+ * Paths are relative to the working directory. Fails if any map has errors.
+ */
+pub fn run(args: &[String]) -> ExitCode {
+    let paths: Vec<&String> = args.iter()
+        .skip_while(|arg| *arg != "--check-map")
+        .skip(1)
+        .take_while(|arg| !arg.starts_with("--"))
+        .collect();
+    if paths.is_empty() {
+        eprintln!("Usage: --check-map <map.tmx>...");
+        return ExitCode::FAILURE;
+    }
+
+    let mut failed = false;
+    for path in paths {
+        let map = match tiled::Loader::new().load_tmx_map(path) {
+            Ok(map) => map,
+            Err(e) => {
+                println!("{path}: could not load: {e}");
+                failed = true;
+                continue;
+            }
+        };
+        let report = validate_map(&map);
+        if report.issues.is_empty() {
+            println!("{path}: ok");
+            continue;
+        }
+        failed |= report.has_errors();
+        print!("{path}:\n{report}");
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+/// Anything spawned for a game session, despawned when leaving `AppState::Game`.
+#[derive(Component)]
+pub struct GameEntity;
+
+/**
+ * Moved by the simulation, drawn between its last two simulated positions.
+ * The simulation owns `current`, the `Transform` is only interpolated for rendering.
+ */
+#[derive(Component)]
+pub struct Interpolated {
+    pub previous: Vec3,
+    pub current: Vec3,
+}
+
+impl Interpolated {
+    pub fn new(translation: Vec3) -> Self {
+        Self {
+            previous: translation,
+            current: translation,
+        }
+    }
+}
@@ -1,7 +1,12 @@
+use std::collections::HashMap;
+
 use bevy::prelude::*;
+use serde::Deserialize;
 
 #[derive(Component)]
-pub struct Enemy {}
+pub struct Enemy {
+    pub kind: String,
+}
 
 #[derive(Component)]
 pub struct Health {
@@ -13,10 +18,30 @@ impl Health {
     pub fn full(max: i32) -> Self { Health { current: max, max } }
 }
 
+/// Gold earned by the player when this enemy is killed.
 #[derive(Component)]
-pub struct Waypoint {
-    pub index: i32,
-    pub position: Vec2,
+pub struct Bounty(pub u32);
+
+/// Lives lost by the player when this enemy reaches the finish.
+#[derive(Component)]
+pub struct LeakCost(pub u32);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub enum DamageType {
+    Physical,
+    Explosive,
+    Frost,
+}
+
+#[derive(Component, Default)]
+pub struct Resistances(pub HashMap<DamageType, f32>);
+
+impl Resistances {
+    /// Multiplier to apply to incoming damage of the given type.
+    pub fn multiplier(&self, damage_type: DamageType) -> f32 {
+        let resistance = self.0.get(&damage_type).copied().unwrap_or(0.0);
+        (1.0 - resistance).clamp(0.0, 1.0)
+    }
 }
 
 #[derive(Component)]
@@ -27,8 +52,11 @@ pub struct Velocity {
 
 #[derive(Component)]
 pub struct EnemySpawner {
-    pub timer: Timer,
+    /// Wave groups pick their spawner by this name.
+    pub name: String,
     pub position: Vec2,
+    /// Name of the path enemies spawned here follow.
+    pub path: String,
 }
 
 #[derive(Component)]
@@ -38,6 +66,9 @@ pub struct EnemyFinish {
 
 #[derive(Component)]
 pub struct WaypointFollower {
+    /// Name of the followed `Path`, empty for the only path of the map.
+    pub path: String,
+    /// Index in the `Path` waypoints of the next one to reach, past the last one the creep heads to the finish.
     pub index: i32,
 }
 
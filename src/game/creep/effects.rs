@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of poison stacks on a single enemy.
+const MAX_POISON_STACKS: usize = 5;
+/// Maximum extra damage taken from armor shred, e.g. 1.0 for twice the damage.
+const MAX_ARMOR_SHRED: f32 = 1.0;
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum StatusEffectKind {
+    /// Speed is multiplied by `1.0 - factor`.
+    Slow { factor: f32 },
+    /// Can't move at all.
+    Freeze,
+    /// Loses health over time.
+    Poison { damage_per_second: f32 },
+    /// Takes `amount` more damage, e.g. 0.2 for 20% more.
+    ArmorShred { amount: f32 },
+}
+
+#[derive(Clone, Debug)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    pub timer: Timer,
+}
+
+impl StatusEffect {
+    pub fn new(kind: StatusEffectKind, duration: f32) -> Self {
+        Self {
+            kind,
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+}
+
+/**
+ * Every status effect currently affecting an enemy.
+ *
+ * Stacking rules :
+ * - Slow : only the strongest slow is kept, an equal or stronger one replaces it.
+ * - Freeze : reapplying extends the duration if the new one is longer.
+ * - Poison : up to `MAX_POISON_STACKS` independent stacks, the closest to expire is replaced when full.
+ * - ArmorShred : independent stacks, the total extra damage is capped at `MAX_ARMOR_SHRED`.
+ */
+#[derive(Component, Default)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+    /// Poison damage not dealt yet because health is an integer.
+    pub pending_damage: f32,
+}
+
+impl StatusEffects {
+    pub fn apply(&mut self, effect: StatusEffect) {
+        match effect.kind {
+            StatusEffectKind::Slow { factor } => {
+                if let Some(current) = self.effects.iter_mut().find(|current| matches!(current.kind, StatusEffectKind::Slow { .. })) {
+                    if let StatusEffectKind::Slow { factor: current_factor } = current.kind {
+                        if factor >= current_factor {
+                            *current = effect;
+                        }
+                    }
+                    return;
+                }
+                self.effects.push(effect);
+            }
+            StatusEffectKind::Freeze => {
+                if let Some(current) = self.effects.iter_mut().find(|current| current.kind == StatusEffectKind::Freeze) {
+                    if effect.timer.remaining() > current.timer.remaining() {
+                        *current = effect;
+                    }
+                    return;
+                }
+                self.effects.push(effect);
+            }
+            StatusEffectKind::Poison { .. } => {
+                let poison_stacks = self.effects.iter()
+                    .filter(|current| matches!(current.kind, StatusEffectKind::Poison { .. }))
+                    .count();
+                if poison_stacks >= MAX_POISON_STACKS {
+                    let closest_to_expire = self.effects.iter()
+                        .enumerate()
+                        .filter(|(_, current)| matches!(current.kind, StatusEffectKind::Poison { .. }))
+                        .min_by_key(|(_, current)| current.timer.remaining())
+                        .map(|(index, _)| index);
+                    if let Some(index) = closest_to_expire {
+                        self.effects[index] = effect;
+                    }
+                    return;
+                }
+                self.effects.push(effect);
+            }
+            StatusEffectKind::ArmorShred { .. } => {
+                self.effects.push(effect);
+            }
+        }
+    }
+
+    /// Multiplier to apply to the speed of the enemy.
+    pub fn speed_multiplier(&self) -> f32 {
+        let mut multiplier: f32 = 1.0;
+        for effect in self.effects.iter() {
+            match effect.kind {
+                StatusEffectKind::Freeze => return 0.0,
+                StatusEffectKind::Slow { factor } => multiplier = multiplier.min(1.0 - factor),
+                _ => {}
+            }
+        }
+        multiplier.max(0.0)
+    }
+
+    /// Multiplier to apply to the damage taken by the enemy.
+    pub fn damage_taken_multiplier(&self) -> f32 {
+        let shred: f32 = self.effects.iter()
+            .map(|effect| match effect.kind {
+                StatusEffectKind::ArmorShred { amount } => amount,
+                _ => 0.0,
+            })
+            .sum();
+        1.0 + shred.min(MAX_ARMOR_SHRED)
+    }
+
+    /// Advances every effect, drops the expired ones and returns the poison damage to deal now.
+    pub fn tick(&mut self, delta: Duration) -> i32 {
+        for effect in self.effects.iter_mut() {
+            if let StatusEffectKind::Poison { damage_per_second } = effect.kind {
+                // do not deal damage past the end of the effect
+                let active = delta.min(effect.timer.remaining());
+                self.pending_damage += damage_per_second * active.as_secs_f32();
+            }
+            effect.timer.tick(delta);
+        }
+        self.effects.retain(|effect| !effect.timer.finished());
+
+        let damage = self.pending_damage.floor();
+        self.pending_damage -= damage;
+        damage as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slow(factor: f32, duration: f32) -> StatusEffect {
+        StatusEffect::new(StatusEffectKind::Slow { factor }, duration)
+    }
+
+    fn poison(duration: f32) -> StatusEffect {
+        StatusEffect::new(StatusEffectKind::Poison { damage_per_second: 10.0 }, duration)
+    }
+
+    #[test]
+    fn only_the_strongest_slow_is_kept() {
+        let mut status_effects = StatusEffects::default();
+        status_effects.apply(slow(0.3, 2.0));
+        status_effects.apply(slow(0.2, 5.0));
+        assert_eq!(status_effects.effects.len(), 1);
+        assert!((status_effects.speed_multiplier() - 0.7).abs() < 1e-6);
+
+        status_effects.apply(slow(0.5, 1.0));
+        assert_eq!(status_effects.effects.len(), 1);
+        assert_eq!(status_effects.speed_multiplier(), 0.5);
+    }
+
+    #[test]
+    fn freeze_keeps_the_longest_duration() {
+        let mut status_effects = StatusEffects::default();
+        status_effects.apply(StatusEffect::new(StatusEffectKind::Freeze, 2.0));
+        status_effects.apply(StatusEffect::new(StatusEffectKind::Freeze, 1.0));
+        assert_eq!(status_effects.effects.len(), 1);
+        assert_eq!(status_effects.effects[0].timer.remaining(), Duration::from_secs(2));
+
+        status_effects.apply(StatusEffect::new(StatusEffectKind::Freeze, 3.0));
+        assert_eq!(status_effects.effects[0].timer.remaining(), Duration::from_secs(3));
+        assert_eq!(status_effects.speed_multiplier(), 0.0);
+    }
+
+    #[test]
+    fn poison_replaces_the_stack_closest_to_expire_when_full() {
+        let mut status_effects = StatusEffects::default();
+        for stack in 0..MAX_POISON_STACKS {
+            status_effects.apply(poison(2.0 + stack as f32));
+        }
+        status_effects.apply(poison(10.0));
+        assert_eq!(status_effects.effects.len(), MAX_POISON_STACKS);
+        let shortest = status_effects.effects.iter().map(|effect| effect.timer.remaining()).min();
+        assert_eq!(shortest, Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn armor_shred_stacks_up_to_the_cap() {
+        let mut status_effects = StatusEffects::default();
+        for _ in 0..3 {
+            status_effects.apply(StatusEffect::new(StatusEffectKind::ArmorShred { amount: 0.4 }, 5.0));
+        }
+        assert_eq!(status_effects.effects.len(), 3);
+        assert_eq!(status_effects.damage_taken_multiplier(), 1.0 + MAX_ARMOR_SHRED);
+    }
+
+    #[test]
+    fn poison_damage_stops_with_the_effect() {
+        let mut status_effects = StatusEffects::default();
+        status_effects.apply(poison(0.5));
+        assert_eq!(status_effects.tick(Duration::from_secs(1)), 5);
+        assert!(status_effects.effects.is_empty());
+        assert_eq!(status_effects.tick(Duration::from_secs(1)), 0);
+    }
+}
@@ -3,3 +3,23 @@ use bevy::prelude::*;
 pub struct KilledEvent {
     pub who: Entity,
 }
+
+pub struct EnemyLeakedEvent {
+    pub who: Entity,
+    /// Lives lost by the player.
+    pub cost: u32,
+}
+
+pub struct SpawnEnemyEvent {
+    pub kind: String,
+    /// Name of the spawner, `None` for the first one of the map.
+    pub spawner: Option<String>,
+}
+
+pub struct WaveStartedEvent {
+    pub index: usize,
+}
+
+pub struct WaveClearedEvent {
+    pub index: usize,
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use bevy::{
+    asset::{AssetLoader, LoadedAsset},
+    log,
+    reflect::TypeUuid,
+};
+use serde::Deserialize;
+
+use super::components::DamageType;
+
+/// Every enemy kind a level can spawn, by name, loaded from a `*.enemies.ron` file.
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "90dc15e0-a995-4dfe-9c68-2f010a14f69b"]
+pub struct EnemyKinds {
+    pub kinds: HashMap<String, EnemyKind>,
+}
+
+#[derive(Deserialize)]
+pub struct EnemyKind {
+    pub health: i32,
+    pub speed: f32,
+    /// Asset path of the sprite.
+    pub sprite: String,
+    pub healthbar: HealthbarSize,
+    /// Gold earned by the player when this enemy is killed.
+    #[serde(default)]
+    pub bounty: u32,
+    /// Lives lost by the player when this enemy reaches the finish.
+    #[serde(default = "default_leak_cost")]
+    pub leak_cost: u32,
+    /// Fraction of the damage ignored for each damage type, from 0.0 to 1.0.
+    #[serde(default)]
+    pub resistances: HashMap<DamageType, f32>,
+}
+
+fn default_leak_cost() -> u32 {
+    1
+}
+
+#[derive(Deserialize)]
+pub struct HealthbarSize {
+    pub length: f32,
+    pub height: f32,
+}
+
+pub struct EnemyKindsLoader;
+
+impl AssetLoader for EnemyKindsLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut bevy::asset::LoadContext,
+    ) -> bevy::asset::BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let enemy_kinds: EnemyKinds = ron::de::from_bytes(bytes)
+                .map_err(|e| anyhow::anyhow!("Could not load enemy kinds: {e}"))?;
+
+            log::info!("Loaded {} enemy kinds: {}", enemy_kinds.kinds.len(), load_context.path().display());
+
+            load_context.set_default_asset(LoadedAsset::new(enemy_kinds));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["enemies.ron"];
+        EXTENSIONS
+    }
+}
@@ -4,15 +4,25 @@ use bevy::sprite::SpriteSystem;
 
 use components::*;
 use events::*;
+use kinds::*;
 use resources::*;
 use systems::*;
+use waves::*;
 
 use crate::AppState;
+use crate::game::SimulationStage;
+use crate::game::tilemap::MapProcessingSet;
 
 mod systems;
 pub mod components;
-mod resources;
+pub mod resources;
 pub mod events;
+pub mod waves;
+pub mod kinds;
+pub mod effects;
+pub mod path;
+
+pub use systems::spawn_enemy_entity;
 
 pub struct CreepPlugin;
 
@@ -20,6 +30,16 @@ impl Plugin for CreepPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_event::<KilledEvent>()
+            .add_event::<EnemyLeakedEvent>()
+            .add_event::<SpawnEnemyEvent>()
+            .add_event::<WaveStartedEvent>()
+            .add_event::<WaveClearedEvent>()
+        ;
+        app
+            .add_asset::<WaveSchedule>()
+            .add_asset_loader(WaveScheduleLoader)
+            .add_asset::<EnemyKinds>()
+            .add_asset_loader(EnemyKindsLoader)
         ;
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
@@ -33,14 +53,46 @@ impl Plugin for CreepPlugin {
 
         app.add_systems(
             (
+                setup_enemy_kinds,
+                setup_waves,
+            ).in_schedule(OnEnter(AppState::Game))
+        );
+        app.add_systems(
+            (
+                update_waves,
                 spawn_enemy,
-                follow_waypoint,
+            )
+                .chain()
+                .in_set(SimulationStage::Waves)
+                .in_schedule(CoreSchedule::FixedUpdate)
+        );
+        app.add_systems(
+            (
                 reach_waypoint,
+                follow_waypoint,
                 do_move_step,
+            )
+                .chain()
+                .in_set(SimulationStage::Movement)
+                .in_schedule(CoreSchedule::FixedUpdate)
+        );
+        app.add_systems(
+            (
+                tick_status_effects,
                 on_enemy_killed,
-                despawn_dying,
+                on_enemy_leaked,
             )
-                .in_set(OnUpdate(AppState::Game))
+                .chain()
+                .in_set(SimulationStage::Damage)
+                .in_schedule(CoreSchedule::FixedUpdate)
+        );
+        app.add_system(despawn_dying
+            .in_set(SimulationStage::Cleanup)
+            .in_schedule(CoreSchedule::FixedUpdate)
+        );
+        app.add_system(retarget_creeps_after_map_reload
+            .after(MapProcessingSet)
+            .in_set(OnUpdate(AppState::Game))
         );
     }
 }
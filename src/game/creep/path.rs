@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+
+/// Points sampled on each segment of a curved path when the map does not set `samples`.
+pub const DEFAULT_PATH_SAMPLES: u32 = 8;
+
+/// Name of the path made of the Waypoint points without a `path` property.
+pub const DEFAULT_PATH_NAME: &str = "default";
+
+/**
+ * Ordered waypoints enemies walk through, from the spawner to the finish.
+ * Built from an `EnemyPath` polyline or from the indexed `Waypoint` points sharing a `path` property.
+ * Positions are relative to the top left of the tilemap, as for the spawner and the finish.
+ */
+#[derive(Component, Clone, Debug, Default)]
+pub struct Path {
+    /// Spawners and creeps refer to their path by this name.
+    pub name: String,
+    pub waypoints: Vec<Vec2>,
+    /// Where enemies go after the last waypoint, the `finish` property of the path or else the closest finish.
+    pub finish: Option<Vec2>,
+    /// Length from each waypoint to the finish, along the path.
+    remaining_lengths: Vec<f32>,
+}
+
+impl Path {
+    pub fn new(name: String, waypoints: Vec<Vec2>, finish: Option<Vec2>) -> Self {
+        let mut remaining_lengths = vec![0.0; waypoints.len()];
+        let mut next = finish;
+        let mut length = 0.0;
+        for (index, waypoint) in waypoints.iter().enumerate().rev() {
+            length += next.map_or(0.0, |next| waypoint.distance(next));
+            remaining_lengths[index] = length;
+            next = Some(*waypoint);
+        }
+        Self {
+            name,
+            waypoints,
+            finish,
+            remaining_lengths,
+        }
+    }
+
+    /// Path with the given name, or the only path of the map for creeps and spawners which do not name one.
+    pub fn find<'a>(paths: impl IntoIterator<Item = &'a Path>, name: &str) -> Option<&'a Path> {
+        let mut first = None;
+        let mut count = 0;
+        for path in paths {
+            if path.name == name {
+                return Some(path);
+            }
+            first = first.or(Some(path));
+            count += 1;
+        }
+        if name.is_empty() && count == 1 { first } else { None }
+    }
+
+    /// Waypoint a follower with this index heads to, `None` once it has passed the last one.
+    pub fn waypoint(&self, index: i32) -> Option<Vec2> {
+        usize::try_from(index).ok().and_then(|index| self.waypoints.get(index).copied())
+    }
+
+    /// Length left to walk from the waypoint with this index to the finish.
+    pub fn remaining_length(&self, index: i32) -> f32 {
+        usize::try_from(index).ok().and_then(|index| self.remaining_lengths.get(index).copied()).unwrap_or(0.0)
+    }
+}
+
+/**
+ * Samples a Catmull-Rom spline going through all the points, so a polyline drawn in Tiled becomes a smooth curve.
+ * The first and last points are kept, `samples_per_segment` points are generated between two successive points.
+ */
+pub fn sample_catmull_rom(points: &[Vec2], samples_per_segment: u32) -> Vec<Vec2> {
+    if points.len() < 3 || samples_per_segment <= 1 {
+        return points.to_vec();
+    }
+    let mut sampled = Vec::with_capacity((points.len() - 1) * samples_per_segment as usize + 1);
+    for segment in 0..points.len() - 1 {
+        // the ends are repeated, the curve starts and stops exactly on them
+        let p0 = points[segment.saturating_sub(1)];
+        let p1 = points[segment];
+        let p2 = points[segment + 1];
+        let p3 = points[(segment + 2).min(points.len() - 1)];
+        for sample in 0..samples_per_segment {
+            let t = sample as f32 / samples_per_segment as f32;
+            let t2 = t * t;
+            let t3 = t2 * t;
+            sampled.push(0.5 * (
+                2.0 * p1
+                    + (p2 - p0) * t
+                    + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+                    + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3
+            ));
+        }
+    }
+    sampled.extend(points.last());
+    sampled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(name: &str) -> Path {
+        Path::new(name.to_string(), vec![Vec2::ZERO], None)
+    }
+
+    #[test]
+    fn paths_are_found_by_name() {
+        let paths = vec![path("north"), path("south")];
+        assert_eq!(Path::find(&paths, "south").map(|path| path.name.as_str()), Some("south"));
+        assert!(Path::find(&paths, "east").is_none());
+    }
+
+    #[test]
+    fn empty_name_only_finds_the_only_path() {
+        let paths = vec![path("north")];
+        assert_eq!(Path::find(&paths, "").map(|path| path.name.as_str()), Some("north"));
+
+        let paths = vec![path("north"), path("south")];
+        assert!(Path::find(&paths, "").is_none());
+        assert!(Path::find(&Vec::<Path>::new(), "").is_none());
+    }
+
+    #[test]
+    fn remaining_lengths_go_to_the_finish() {
+        let path = Path::new(
+            "path".to_string(),
+            vec![Vec2::new(0.0, 0.0), Vec2::new(30.0, 0.0), Vec2::new(30.0, 40.0)],
+            Some(Vec2::new(30.0, 50.0)),
+        );
+        assert_eq!(path.remaining_length(0), 80.0);
+        assert_eq!(path.remaining_length(1), 50.0);
+        assert_eq!(path.remaining_length(2), 10.0);
+        // past the last waypoint, or before the first one
+        assert_eq!(path.remaining_length(3), 0.0);
+        assert_eq!(path.remaining_length(-1), 0.0);
+    }
+
+    #[test]
+    fn remaining_lengths_without_finish_end_at_the_last_waypoint() {
+        let path = Path::new("path".to_string(), vec![Vec2::new(0.0, 0.0), Vec2::new(0.0, 20.0)], None);
+        assert_eq!(path.remaining_length(0), 20.0);
+        assert_eq!(path.remaining_length(1), 0.0);
+    }
+
+    #[test]
+    fn short_polylines_are_not_sampled() {
+        let points = vec![Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0)];
+        assert_eq!(sample_catmull_rom(&points, 8), points);
+
+        let points = vec![Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0)];
+        assert_eq!(sample_catmull_rom(&points, 1), points);
+    }
+
+    #[test]
+    fn spline_goes_through_every_point() {
+        let points = vec![Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0), Vec2::new(20.0, 10.0)];
+        let sampled = sample_catmull_rom(&points, 4);
+        assert_eq!(sampled.len(), 3 * 4 + 1);
+        for (index, point) in points.iter().enumerate() {
+            assert!(sampled[index * 4].distance(*point) < 1e-4, "{:?} is not on the spline", point);
+        }
+    }
+
+    #[test]
+    fn spline_of_aligned_points_stays_on_the_line() {
+        let points = vec![Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(20.0, 0.0)];
+        let sampled = sample_catmull_rom(&points, 5);
+        assert!(sampled.iter().all(|point| point.y.abs() < 1e-4));
+        assert!(sampled.windows(2).all(|pair| pair[1].x > pair[0].x));
+    }
+}
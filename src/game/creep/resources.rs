@@ -1 +1,48 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::kinds::EnemyKinds;
+use super::waves::WaveSchedule;
+
+#[derive(Resource)]
+pub struct EnemyRegistry {
+    pub kinds: Handle<EnemyKinds>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum WavePhase {
+    /// The schedule asset is not loaded yet.
+    Loading,
+    /// Waiting before the next wave starts.
+    Countdown,
+    /// The current wave still has enemies to spawn.
+    Spawning,
+    /// Everything has been spawned, waiting for the last enemies to die or leak.
+    Clearing,
+    /// All the waves have been cleared.
+    Finished,
+}
+
+#[derive(Resource)]
+pub struct WaveState {
+    pub schedule: Handle<WaveSchedule>,
+    pub phase: WavePhase,
+    pub wave_index: usize,
+    pub group_index: usize,
+    pub spawned_in_group: u32,
+    /// Countdown before the next wave, or spacing before the next spawn.
+    pub timer: Timer,
+}
+
+impl WaveState {
+    pub fn new(schedule: Handle<WaveSchedule>) -> Self {
+        Self {
+            schedule,
+            phase: WavePhase::Loading,
+            wave_index: 0,
+            group_index: 0,
+            spawned_in_group: 0,
+            timer: Timer::default(),
+        }
+    }
+}
@@ -1,66 +1,228 @@
 use std::cmp::max;
 use bevy::math::Vec3Swizzles;
+use bevy::log;
 use bevy::prelude::*;
 use bevy::render::Extract;
 use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
 use bevy::sprite::{Anchor, ExtractedSprite, ExtractedSprites};
 use bevy_ecs_tilemap::prelude::*;
+use crate::AppState;
+use crate::game::components::{GameEntity, Interpolated};
+use crate::game::resources::{CurrentLevel, GameStats, Gold, PlayerLives, SimulationClock};
+use crate::game::tilemap::events::MapReloadedEvent;
+
 use super::components::*;
+use super::effects::StatusEffects;
 use super::events::*;
+use super::kinds::*;
+use super::path::Path;
+use super::resources::*;
+use super::waves::*;
 
-pub fn spawn_enemy(
+pub fn setup_enemy_kinds(
     mut commands: Commands,
-    mut enemy_spawner_query: Query<&mut EnemySpawner>,
     asset_server: Res<AssetServer>,
-    tile_map_query: Query<(&GlobalTransform, &TilemapTileSize), With<TileStorage>>,
-    time: Res<Time>,
 ) {
-    let Ok(mut enemy_spawner) = enemy_spawner_query.get_single_mut() else {
+    commands.insert_resource(EnemyRegistry {
+        kinds: asset_server.load("enemies/default.enemies.ron"),
+    });
+}
+
+pub fn setup_waves(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    current_level: Res<CurrentLevel>,
+) {
+    commands.insert_resource(WaveState::new(asset_server.load(current_level.waves.as_str())));
+}
+
+/**
+ * Drives the wave schedule : countdown, spawns of each group, then waits for the wave to be cleared.
+ */
+pub fn update_waves(
+    mut wave_state: ResMut<WaveState>,
+    schedules: Res<Assets<WaveSchedule>>,
+    enemy_query: Query<(), With<Enemy>>,
+    mut spawn_enemy_event_writer: EventWriter<SpawnEnemyEvent>,
+    mut wave_started_event_writer: EventWriter<WaveStartedEvent>,
+    mut wave_cleared_event_writer: EventWriter<WaveClearedEvent>,
+    mut game_stats: ResMut<GameStats>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    enemy_registry: Res<EnemyRegistry>,
+    enemy_kinds: Res<Assets<EnemyKinds>>,
+    simulation_clock: Res<SimulationClock>,
+) {
+    let Some(schedule) = schedules.get(&wave_state.schedule) else {
         return;
     };
-
-    enemy_spawner.timer.tick(time.delta());
-    if !enemy_spawner.timer.finished() {
+    if enemy_kinds.get(&enemy_registry.kinds).is_none() {
+        // wait for the enemy kinds to be able to spawn them
         return;
     }
 
-    let (tilemap_transform, tile_size) = tile_map_query.single();
+    match wave_state.phase {
+        WavePhase::Loading => {
+            if schedule.waves.is_empty() {
+                wave_state.phase = WavePhase::Finished;
+                return;
+            }
+            wave_state.timer = Timer::from_seconds(schedule.initial_delay, TimerMode::Once);
+            wave_state.phase = WavePhase::Countdown;
+        }
+        WavePhase::Countdown => {
+            wave_state.timer.tick(simulation_clock.delta());
+            if !wave_state.timer.finished() {
+                return;
+            }
+            log::info!("Wave {} started.", wave_state.wave_index);
+            wave_started_event_writer.send(WaveStartedEvent {
+                index: wave_state.wave_index,
+            });
+            wave_state.group_index = 0;
+            wave_state.spawned_in_group = 0;
+            // first enemy of the wave is spawned right away
+            wave_state.timer = Timer::from_seconds(0.0, TimerMode::Once);
+            wave_state.phase = WavePhase::Spawning;
+        }
+        WavePhase::Spawning => {
+            wave_state.timer.tick(simulation_clock.delta());
+            if !wave_state.timer.finished() {
+                return;
+            }
+            let wave = &schedule.waves[wave_state.wave_index];
+            if let Some(group) = wave.groups.get(wave_state.group_index) {
+                if wave_state.spawned_in_group < group.count {
+                    spawn_enemy_event_writer.send(SpawnEnemyEvent {
+                        kind: group.kind.clone(),
+                        spawner: group.spawner.clone(),
+                    });
+                    wave_state.spawned_in_group += 1;
+                }
+                if wave_state.spawned_in_group >= group.count {
+                    wave_state.group_index += 1;
+                    wave_state.spawned_in_group = 0;
+                }
+            }
+            let Some(next_group) = wave.groups.get(wave_state.group_index) else {
+                wave_state.phase = WavePhase::Clearing;
+                return;
+            };
+            wave_state.timer = Timer::from_seconds(next_group.spacing, TimerMode::Once);
+        }
+        WavePhase::Clearing => {
+            if !enemy_query.is_empty() {
+                return;
+            }
+            log::info!("Wave {} cleared.", wave_state.wave_index);
+            wave_cleared_event_writer.send(WaveClearedEvent {
+                index: wave_state.wave_index,
+            });
+            game_stats.waves_survived += 1;
+            let delay_after = schedule.waves[wave_state.wave_index].delay_after;
+            if wave_state.wave_index + 1 >= schedule.waves.len() {
+                wave_state.phase = WavePhase::Finished;
+                game_stats.victory = true;
+                next_app_state.set(AppState::GameOver);
+                return;
+            }
+            wave_state.wave_index += 1;
+            wave_state.timer = Timer::from_seconds(delay_after, TimerMode::Once);
+            wave_state.phase = WavePhase::Countdown;
+        }
+        WavePhase::Finished => {}
+    }
+}
+
+pub fn spawn_enemy(
+    mut commands: Commands,
+    mut spawn_enemy_event_reader: EventReader<SpawnEnemyEvent>,
+    enemy_spawner_query: Query<&EnemySpawner>,
+    enemy_registry: Res<EnemyRegistry>,
+    enemy_kinds: Res<Assets<EnemyKinds>>,
+    asset_server: Res<AssetServer>,
+    tile_map_query: Query<(&GlobalTransform, &TilemapTileSize), With<TileStorage>>,
+) {
+    let Some(enemy_kinds) = enemy_kinds.get(&enemy_registry.kinds) else {
+        return;
+    };
+    let Ok((tilemap_transform, tile_size)) = tile_map_query.get_single() else {
+        return;
+    };
     let tilemap_top_left = tilemap_transform.translation() - Vec3::new(tile_size.x / 2.0, tile_size.y / 2.0, 0.0);
 
+    for event in spawn_enemy_event_reader.iter() {
+        let Some(enemy_kind) = enemy_kinds.kinds.get(&event.kind) else {
+            log::warn!("Skipped spawn of unknown enemy kind {}.", event.kind);
+            continue;
+        };
+        let enemy_spawner = match &event.spawner {
+            Some(name) => enemy_spawner_query.iter().find(|enemy_spawner| &enemy_spawner.name == name),
+            None => enemy_spawner_query.iter().min_by(|a, b| a.name.cmp(&b.name)),
+        };
+        let Some(enemy_spawner) = enemy_spawner else {
+            log::warn!("Skipped spawn of {} because the map has no spawner {:?}.", event.kind, event.spawner);
+            continue;
+        };
+
+        let translation = Vec3::from((enemy_spawner.position, 10.0)) + tilemap_top_left;
+        spawn_enemy_entity(&mut commands, &asset_server, &event.kind, enemy_kind, &enemy_spawner.path, translation);
+    }
+}
+
+/**
+ * Spawns a fresh enemy of the given kind, with full health and heading to the first waypoint of its path.
+ */
+pub fn spawn_enemy_entity(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    kind: &str,
+    enemy_kind: &EnemyKind,
+    path: &str,
+    translation: Vec3,
+) -> Entity {
     commands.spawn(
         (
-            Enemy {},
-            Health::full(500),
+            Enemy {
+                kind: kind.to_string(),
+            },
+            Health::full(enemy_kind.health),
             Healthbar {
-                length: 64.0,
-                height: 10.0,
+                length: enemy_kind.healthbar.length,
+                height: enemy_kind.healthbar.height,
             },
+            Resistances(enemy_kind.resistances.clone()),
+            Bounty(enemy_kind.bounty),
+            LeakCost(enemy_kind.leak_cost),
+            StatusEffects::default(),
             Velocity {
-                speed: 200.0,
+                speed: enemy_kind.speed,
                 direction: Vec2::new(0.0, 0.0),
             },
             WaypointFollower {
+                path: path.to_string(),
                 index: 0,
             },
             SpriteBundle {
-                transform: Transform::from_translation(Vec3::from((enemy_spawner.position, 10.0)) + tilemap_top_left),
-                texture: asset_server.load("sprites/enemy_1.png"),
+                transform: Transform::from_translation(translation),
+                texture: asset_server.load(enemy_kind.sprite.as_str()),
                 sprite: Sprite {
                     anchor: Anchor::Center,
                     ..default()
                 },
                 ..default()
             },
-            Name::new("Enemy"),
+            Interpolated::new(translation),
+            GameEntity,
+            Name::new(format!("Enemy ({})", kind)),
         ),
-    );
+    ).id()
 }
 
 pub fn follow_waypoint(
     mut commands: Commands,
     mut follower_query: Query<(Entity, &WaypointFollower, &mut Velocity, &Transform)>,
     finish_query: Query<&EnemyFinish>,
-    waypoints_query: Query<&Waypoint>,
+    path_query: Query<&Path>,
     tile_map_query: Query<(&GlobalTransform, &TilemapTileSize), With<TileStorage>>,
 ) {
     let Ok((tilemap_transform, tile_size)) = tile_map_query.get_single() else {
@@ -69,9 +231,10 @@ pub fn follow_waypoint(
     let tilemap_top_left = tilemap_transform.translation() - Vec3::new(tile_size.x / 2.0, tile_size.y / 2.0, 0.0);
 
     for (follower_entity, follower, mut velocity, transform) in follower_query.iter_mut() {
-        let Some(waypoint) = waypoints_query.iter().find(|waypoint| waypoint.index == follower.index) else {
-            if let Ok(finish) = finish_query.get_single() {
-                velocity.direction = ((finish.position + tilemap_top_left.xy()) - transform.translation.xy()).normalize();
+        let path = Path::find(path_query.iter(), &follower.path);
+        let Some(waypoint) = path.and_then(|path| path.waypoint(follower.index)) else {
+            if let Some(finish) = finish_of(path, &finish_query) {
+                velocity.direction = ((finish + tilemap_top_left.xy()) - transform.translation.xy()).normalize();
             } else {
                 // no finish : despawn now
                 println!("despawn creep because no more waypoint {:?}", follower_entity);
@@ -81,63 +244,181 @@ pub fn follow_waypoint(
             continue;
         };
 
-        velocity.direction = ((waypoint.position + tilemap_top_left.xy()) - transform.translation.xy()).normalize();
+        velocity.direction = ((waypoint + tilemap_top_left.xy()) - transform.translation.xy()).normalize();
     }
 }
 
+/**
+ * A waypoint is reached when the next move step would get past it, the creep is then snapped onto it.
+ * Checking against the step length instead of a fixed distance keeps fast creeps on the path at high game speed.
+ */
 pub fn reach_waypoint(
     mut commands: Commands,
-    mut follower_query: Query<(Entity, &mut WaypointFollower, &Transform)>,
+    mut follower_query: Query<(Entity, &mut WaypointFollower, &mut Transform, &Velocity, Option<&StatusEffects>, Option<&LeakCost>)>,
+    mut enemy_leaked_event_writer: EventWriter<EnemyLeakedEvent>,
     finish_query: Query<&EnemyFinish>,
-    waypoints_query: Query<&Waypoint>,
+    path_query: Query<&Path>,
     tile_map_query: Query<(&GlobalTransform, &TilemapTileSize), With<TileStorage>>,
+    simulation_clock: Res<SimulationClock>,
 ) {
     let Ok((tilemap_transform, tile_size)) = tile_map_query.get_single() else {
         return;
     };
     let tilemap_top_left = tilemap_transform.translation() - Vec3::new(tile_size.x / 2.0, tile_size.y / 2.0, 0.0);
 
-    for (follower_entity, mut follower, transform) in follower_query.iter_mut() {
+    for (follower_entity, mut follower, mut transform, velocity, status_effects, leak_cost) in follower_query.iter_mut() {
+        let path = Path::find(path_query.iter(), &follower.path);
+        let speed_multiplier = status_effects.map_or(1.0, |status_effects| status_effects.speed_multiplier());
+        let step = (velocity.speed * speed_multiplier * simulation_clock.delta_seconds()).max(1.0);
 
         // TODO : optimiser en mettant dans le composant directement la position du prochain waypoint
 
-        let Some(waypoint) = waypoints_query.iter().find(|waypoint| waypoint.index == follower.index) else {
+        let Some(waypoint) = path.and_then(|path| path.waypoint(follower.index)) else {
             // finish ?
-            let Ok(finish) = finish_query.get_single() else {
+            let Some(finish) = finish_of(path, &finish_query) else {
                 continue;
             };
 
-            if ((finish.position + tilemap_top_left.xy()) - transform.translation.xy()).length_squared() < 1.0 {
-                // finish reached : TODO : publish event, update score...
+            if ((finish + tilemap_top_left.xy()) - transform.translation.xy()).length_squared() <= step * step {
                 println!("despawn creep because finish reached {:?}", follower_entity);
+                enemy_leaked_event_writer.send(EnemyLeakedEvent {
+                    who: follower_entity,
+                    cost: leak_cost.map_or(1, |leak_cost| leak_cost.0),
+                });
                 commands.entity(follower_entity).despawn_recursive();
             }
 
             continue;
         };
 
-        if ((waypoint.position + tilemap_top_left.xy()) - transform.translation.xy()).length_squared() < 1.0 {
+        let waypoint_position = waypoint + tilemap_top_left.xy();
+        if (waypoint_position - transform.translation.xy()).length_squared() <= step * step {
+            transform.translation.x = waypoint_position.x;
+            transform.translation.y = waypoint_position.y;
             follower.index += 1;
         }
     }
 }
 
+/// Finish at the end of a path, or the first finish of the map for creeps without a path.
+fn finish_of(path: Option<&Path>, finish_query: &Query<&EnemyFinish>) -> Option<Vec2> {
+    match path {
+        Some(path) => path.finish,
+        None => finish_query.iter().next().map(|finish| finish.position),
+    }
+}
+
+fn distance_to_segment(point: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let segment = end - start;
+    if segment.length_squared() == 0.0 {
+        return point.distance(start);
+    }
+    let along = ((point - start).dot(segment) / segment.length_squared()).clamp(0.0, 1.0);
+    point.distance(start + segment * along)
+}
+
+/**
+ * After a map reload, every creep heads to the end of the closest leg of the new path,
+ * so it does not walk back to a waypoint which moved behind it.
+ */
+pub fn retarget_creeps_after_map_reload(
+    mut map_reloaded_event_reader: EventReader<MapReloadedEvent>,
+    mut follower_query: Query<(&mut WaypointFollower, &Interpolated)>,
+    spawner_query: Query<&EnemySpawner>,
+    finish_query: Query<&EnemyFinish>,
+    path_query: Query<&Path>,
+    tile_map_query: Query<(&GlobalTransform, &TilemapTileSize), With<TileStorage>>,
+) {
+    if map_reloaded_event_reader.iter().count() == 0 {
+        return;
+    }
+    let Ok((tilemap_transform, tile_size)) = tile_map_query.get_single() else {
+        return;
+    };
+    let tilemap_top_left = tilemap_transform.translation().xy() - Vec2::new(tile_size.x / 2.0, tile_size.y / 2.0);
+
+    for (mut follower, interpolated) in follower_query.iter_mut() {
+        let Some(path) = Path::find(path_query.iter(), &follower.path) else {
+            // the path is gone, the creep heads to a finish
+            continue;
+        };
+
+        // legs of the path as (index the creep follows, start, end), the finish is reached past the last waypoint
+        let mut points: Vec<(i32, Vec2)> = path.waypoints.iter()
+            .enumerate()
+            .map(|(index, position)| (index as i32, *position + tilemap_top_left))
+            .collect();
+        if let Some(finish) = finish_of(Some(path), &finish_query) {
+            points.push((points.len() as i32, finish + tilemap_top_left));
+        }
+        let Some(&(_, first_point)) = points.first() else {
+            continue;
+        };
+        let start = spawner_query.iter()
+            .find(|spawner| spawner.path == path.name)
+            .map_or(first_point, |spawner| spawner.position + tilemap_top_left);
+        let mut legs = Vec::with_capacity(points.len());
+        let mut previous = start;
+        for (index, point) in points.iter() {
+            legs.push((*index, previous, *point));
+            previous = *point;
+        }
+
+        let position = interpolated.current.xy();
+        let closest_leg = legs.iter()
+            .min_by(|(_, start_a, end_a), (_, start_b, end_b)| {
+                distance_to_segment(position, *start_a, *end_a)
+                    .total_cmp(&distance_to_segment(position, *start_b, *end_b))
+            });
+        if let Some((index, _, _)) = closest_leg {
+            follower.index = *index;
+        }
+    }
+}
+
 pub fn on_enemy_killed(
     mut commands: Commands,
     mut event_reader: EventReader<KilledEvent>,
-    enemy_query: Query<&Enemy, Without<Dying>>,
+    enemy_query: Query<Option<&Bounty>, (With<Enemy>, Without<Dying>)>,
+    mut gold: ResMut<Gold>,
+    mut game_stats: ResMut<GameStats>,
 ) {
+    let mut killed = Vec::<Entity>::new();
     for event in event_reader.iter() {
+        // several projectiles can kill the same enemy during the same frame
+        if killed.contains(&event.who) {
+            continue;
+        }
+        let Ok(bounty) = enemy_query.get(event.who) else {
+            continue;
+        };
+        killed.push(event.who);
+        game_stats.enemies_killed += 1;
+        if let Some(bounty) = bounty {
+            gold.current += bounty.0;
+            game_stats.gold_earned += bounty.0;
+        }
         if let Some(mut who_entity) = commands.get_entity(event.who) {
-            // println!("despawn creep because killed {:?}", event.who);
-            // who_entity.despawn_recursive();
             println!("set dying creep because killed {:?}", event.who);
             who_entity.insert(Dying);
         }
-        // enemy_query.get(event.who).ok().map(|enemy| {
-        //     // TODO : add points ?
-        //     commands.entity(event.who).despawn_recursive();
-        // });
+    }
+}
+
+pub fn on_enemy_leaked(
+    mut event_reader: EventReader<EnemyLeakedEvent>,
+    mut player_lives: ResMut<PlayerLives>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    for event in event_reader.iter() {
+        if player_lives.current == 0 {
+            break;
+        }
+        player_lives.current = player_lives.current.saturating_sub(event.cost);
+        log::info!("Creep {:?} leaked, {} lives left.", event.who, player_lives.current);
+        if player_lives.current == 0 {
+            next_app_state.set(AppState::GameOver);
+        }
     }
 }
 
@@ -152,11 +433,12 @@ pub fn despawn_dying(
 }
 
 pub fn do_move_step(
-    mut move_query: Query<(&Velocity, &mut Transform)>,
-    time: Res<Time>,
+    mut move_query: Query<(&Velocity, &mut Transform, Option<&StatusEffects>)>,
+    simulation_clock: Res<SimulationClock>,
 ) {
-    for (velocity, mut transform) in move_query.iter_mut() {
-        transform.translation += Vec3::from((velocity.direction, 0.0)) * velocity.speed * time.delta_seconds();
+    for (velocity, mut transform, status_effects) in move_query.iter_mut() {
+        let speed_multiplier = status_effects.map_or(1.0, |status_effects| status_effects.speed_multiplier());
+        transform.translation += Vec3::from((velocity.direction, 0.0)) * velocity.speed * speed_multiplier * simulation_clock.delta_seconds();
         transform.rotation = Quat::from_rotation_arc_2d(
             Vec2::new(1.0, 0.0),
             velocity.direction,
@@ -164,6 +446,31 @@ pub fn do_move_step(
     }
 }
 
+/**
+ * Makes status effects expire and deals poison damage.
+ */
+pub fn tick_status_effects(
+    mut status_effects_query: Query<(Entity, &mut StatusEffects, &mut Health), Without<Dying>>,
+    mut event_writer: EventWriter<KilledEvent>,
+    simulation_clock: Res<SimulationClock>,
+) {
+    for (entity, mut status_effects, mut health) in status_effects_query.iter_mut() {
+        if status_effects.effects.is_empty() {
+            continue;
+        }
+        let damage = status_effects.tick(simulation_clock.delta());
+        if damage <= 0 || health.current <= 0 {
+            continue;
+        }
+        health.current -= damage;
+        if health.current <= 0 {
+            event_writer.send(KilledEvent {
+                who: entity,
+            });
+        }
+    }
+}
+
 pub fn extract_health_bar(
     mut extracted_sprites: ResMut<ExtractedSprites>,
     healthbar_query: Extract<
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use bevy::{
+    asset::{AssetLoader, LoadedAsset},
+    log,
+    reflect::TypeUuid,
+};
+use serde::Deserialize;
+
+/// Ordered list of waves a level throws at the player, loaded from a `*.waves.ron` file.
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "a04308b9-8253-4c5a-a2fc-91f3508137bf"]
+pub struct WaveSchedule {
+    /// Seconds before the first wave starts.
+    pub initial_delay: f32,
+    pub waves: Vec<Wave>,
+}
+
+#[derive(Deserialize)]
+pub struct Wave {
+    /// Groups are spawned one after another.
+    pub groups: Vec<WaveGroup>,
+    /// Seconds between the moment this wave is cleared and the start of the next one.
+    pub delay_after: f32,
+}
+
+#[derive(Deserialize)]
+pub struct WaveGroup {
+    /// Name of the enemy kind to spawn.
+    pub kind: String,
+    pub count: u32,
+    /// Seconds between two spawns of this group.
+    pub spacing: f32,
+    /// Name of the spawner in the map, the first one by name when not set.
+    #[serde(default)]
+    pub spawner: Option<String>,
+}
+
+pub struct WaveScheduleLoader;
+
+impl AssetLoader for WaveScheduleLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut bevy::asset::LoadContext,
+    ) -> bevy::asset::BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let schedule: WaveSchedule = ron::de::from_bytes(bytes)
+                .map_err(|e| anyhow::anyhow!("Could not load wave schedule: {e}"))?;
+
+            log::info!("Loaded {} waves: {}", schedule.waves.len(), load_context.path().display());
+
+            load_context.set_default_asset(LoadedAsset::new(schedule));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["waves.ron"];
+        EXTENSIONS
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use bevy::{
+    asset::{AssetLoader, LoadedAsset},
+    log,
+    prelude::*,
+    reflect::TypeUuid,
+};
+use serde::Deserialize;
+
+use crate::game::resources::CurrentLevel;
+
+/// Path of the catalogue listing every playable level.
+pub const LEVEL_CATALOG_PATH: &str = "levels/catalog.levels.ron";
+
+/// Every level the player can pick, loaded from a `*.levels.ron` file.
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "3e6f0c3a-1b0d-4d8e-b2a1-6c4f9e2d7a15"]
+pub struct LevelCatalog {
+    pub levels: Vec<LevelEntry>,
+}
+
+#[derive(Deserialize)]
+pub struct LevelEntry {
+    /// Shown on the level select screen.
+    pub name: String,
+    /// Tiled map, relative to the assets folder.
+    pub map: String,
+    pub waves: String,
+    /// Image shown on the level select screen.
+    pub thumbnail: String,
+    pub starting_gold: u32,
+    pub starting_lives: u32,
+}
+
+impl LevelEntry {
+    pub fn level(&self) -> CurrentLevel {
+        CurrentLevel {
+            map: self.map.clone(),
+            waves: self.waves.clone(),
+            starting_gold: self.starting_gold,
+            starting_lives: self.starting_lives,
+        }
+    }
+}
+
+/// Handle keeping the catalogue loaded.
+#[derive(Resource)]
+pub struct LevelCatalogHandle(pub Handle<LevelCatalog>);
+
+pub struct LevelCatalogLoader;
+
+impl AssetLoader for LevelCatalogLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut bevy::asset::LoadContext,
+    ) -> bevy::asset::BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let catalog: LevelCatalog = ron::de::from_bytes(bytes)
+                .map_err(|e| anyhow::anyhow!("Could not load level catalog: {e}"))?;
+
+            log::info!("Loaded {} levels: {}", catalog.levels.len(), load_context.path().display());
+
+            load_context.set_default_asset(LoadedAsset::new(catalog));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["levels.ron"];
+        EXTENSIONS
+    }
+}
@@ -1,19 +1,30 @@
 use bevy::prelude::*;
 
 use creep::CreepPlugin;
+use levels::{LevelCatalog, LevelCatalogLoader};
+use player_commands::PlayerCommandsPlugin;
+use save::resources::PendingLoad;
+use save::SavePlugin;
 use systems::*;
 use tilemap::TilemapPlugin;
 use tower::TowerPlugin;
 use ui::UiPlugin;
 use crate::AppState;
-use crate::game::resources::BuildTower;
+use crate::game::creep::kinds::EnemyKinds;
+use crate::game::creep::resources::{EnemyRegistry, WaveState};
+use crate::game::creep::waves::WaveSchedule;
+use crate::game::resources::{BuildTower, CurrentLevel, GameStats, Gold, PlayerLives, SimulationClock};
 
-mod tilemap;
-mod creep;
-mod tower;
+pub mod tilemap;
+pub mod creep;
+pub mod tower;
+pub mod player_commands;
+pub mod save;
+pub mod levels;
 mod systems;
 mod ui;
-mod resources;
+pub mod components;
+pub mod resources;
 
 pub struct GamePlugin;
 
@@ -23,16 +34,101 @@ impl Plugin for GamePlugin {
             .add_state::<GameState>()
             .add_state::<UiState>()
         ;
+        app.init_resource::<CurrentLevel>();
+        app
+            .add_asset::<LevelCatalog>()
+            .add_asset_loader(LevelCatalogLoader)
+        ;
         app.init_resource::<BuildTower>();
+        app.init_resource::<PlayerLives>();
+        app.init_resource::<Gold>();
+        app.init_resource::<GameStats>();
+        app.init_resource::<SimulationClock>();
+        app.insert_resource(FixedTime::new_from_secs(SIMULATION_STEP));
+        app.edit_schedule(CoreSchedule::FixedUpdate, |schedule| {
+            schedule
+                .configure_set(
+                    SimulationSet
+                        .run_if(in_state(AppState::Game))
+                        .run_if(is_simulation_running)
+                        .run_if(is_level_loaded)
+                        .run_if(not(resource_exists::<PendingLoad>()))
+                )
+                .configure_sets(
+                    (
+                        SimulationStage::Prepare,
+                        SimulationStage::PlayerCommands,
+                        SimulationStage::Building,
+                        SimulationStage::Waves,
+                        SimulationStage::Movement,
+                        SimulationStage::Towers,
+                        SimulationStage::Hits,
+                        SimulationStage::Damage,
+                        SimulationStage::Cleanup,
+                    )
+                        .chain()
+                        .in_set(SimulationSet)
+                )
+                // spawned creeps and dying ones must be visible to the rest of the step
+                .add_system(apply_system_buffers
+                    .after(SimulationStage::Waves)
+                    .before(SimulationStage::Movement)
+                )
+                .add_system(apply_system_buffers
+                    .after(SimulationStage::Damage)
+                    .before(SimulationStage::Cleanup)
+                );
+        });
         app
             .add_plugin(TilemapPlugin)
             .add_plugin(CreepPlugin)
             .add_plugin(TowerPlugin)
-            .add_plugin(UiPlugin)
+            .add_plugin(PlayerCommandsPlugin)
+            .add_plugin(SavePlugin)
+        ;
+        app
+            .add_system(reset_game_resources.in_schedule(OnEnter(AppState::Game)))
+            .add_systems((toggle_pause, change_game_speed).in_set(OnUpdate(AppState::Game)))
+            .add_systems(
+                (
+                    restore_simulated_translations,
+                    speed_up_fixed_time.run_if(in_state(AppState::Game)),
+                ).in_base_set(CoreSet::PreUpdate)
+            )
+            .add_systems(
+                (
+                    tick_simulation_clock,
+                    store_previous_translations,
+                )
+                    .in_set(SimulationStage::Prepare)
+                    .in_schedule(CoreSchedule::FixedUpdate)
+            )
+            .add_system(store_current_translations
+                .in_set(SimulationStage::Cleanup)
+                .in_schedule(CoreSchedule::FixedUpdate)
+            )
+            .add_system(interpolate_translations
+                .run_if(is_simulation_running)
+                .in_set(OnUpdate(AppState::Game))
+            )
+            .add_systems((despawn_game_entities, reset_game_states).in_schedule(OnExit(AppState::Game)))
         ;
+    }
+}
+
+/**
+ * What is only needed to show the game to a player : tilemap rendering, UI and camera.
+ * The simulation in `GamePlugin` runs without it, e.g. in headless mode.
+ */
+pub struct GameViewPlugin;
+
+impl Plugin for GameViewPlugin {
+    fn build(&self, app: &mut App) {
         app
-            .add_system(move_camera.in_set(OnUpdate(AppState::Game)))
+            .add_plugin(bevy_ecs_tilemap::TilemapPlugin)
+            .add_plugin(UiPlugin)
         ;
+        app.add_system(move_camera.in_set(OnUpdate(AppState::Game)));
     }
 }
 
@@ -50,3 +146,62 @@ pub enum UiState {
     Nothing,
     ChoosingAction,
 }
+
+/// Duration of one simulation step, in seconds.
+pub const SIMULATION_STEP: f32 = 1.0 / 60.0;
+
+/**
+ * Systems advancing the game world : creeps, waves, towers, projectiles.
+ * They run in `CoreSchedule::FixedUpdate` so every step lasts exactly `SIMULATION_STEP`,
+ * whatever the framerate, and the same inputs always give the same game.
+ * Nothing in this set runs while the game is paused, so their timers are frozen too.
+ */
+#[derive(SystemSet, Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SimulationSet;
+
+/**
+ * Explicit order of the simulation inside a step.
+ * Commands are applied after `Waves` and after `Damage`, other stages see them on the next step.
+ */
+#[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SimulationStage {
+    Prepare,
+    /// Recorded player commands become events.
+    PlayerCommands,
+    /// Towers are built, upgraded and sold.
+    Building,
+    Waves,
+    Movement,
+    Towers,
+    /// Projectile hits become damage.
+    Hits,
+    /// Damage over time, kills and leaks.
+    Damage,
+    Cleanup,
+}
+
+pub fn is_simulation_running(
+    game_state: Res<State<GameState>>,
+) -> bool {
+    game_state.0 != GameState::Paused
+}
+
+/**
+ * The first step only runs once the map, the waves and the enemies are loaded,
+ * so a replay sees the same ticks however long loading took. A saved game is also restored before it.
+ */
+pub fn is_level_loaded(
+    tilemap_query: Query<(), With<bevy_ecs_tilemap::prelude::TileStorage>>,
+    wave_state: Option<Res<WaveState>>,
+    schedules: Res<Assets<WaveSchedule>>,
+    enemy_registry: Option<Res<EnemyRegistry>>,
+    enemy_kinds: Res<Assets<EnemyKinds>>,
+) -> bool {
+    // run conditions of a set are all evaluated, even outside of a game where these resources don't exist yet
+    let (Some(wave_state), Some(enemy_registry)) = (wave_state, enemy_registry) else {
+        return false;
+    };
+    !tilemap_query.is_empty()
+        && schedules.get(&wave_state.schedule).is_some()
+        && enemy_kinds.get(&enemy_registry.kinds).is_some()
+}
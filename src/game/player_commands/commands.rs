@@ -0,0 +1,46 @@
+use bevy_ecs_tilemap::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::resources::TowerType;
+use crate::game::tower::targeting::TargetingMode;
+
+/**
+ * Every action a player can take on a game. Towers are designated by their tile,
+ * which unlike entities stays the same from one run to the next.
+ */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PlayerCommand {
+    BuildTower { tower_type: TowerType, tile: (u32, u32) },
+    UpgradeTower { tile: (u32, u32) },
+    SellTower { tile: (u32, u32) },
+    SetTargeting { tile: (u32, u32), mode: TargetingMode },
+    SetSpeed { speed: f32 },
+    Pause,
+    Resume,
+}
+
+impl PlayerCommand {
+    /// Commands changing the outcome of the game, applied at the start of a simulation step.
+    /// The others only change how the game is watched and are applied right away.
+    pub fn affects_simulation(&self) -> bool {
+        !matches!(self, PlayerCommand::SetSpeed { .. } | PlayerCommand::Pause | PlayerCommand::Resume)
+    }
+}
+
+pub fn tile_of(tile_pos: &TilePos) -> (u32, u32) {
+    (tile_pos.x, tile_pos.y)
+}
+
+pub fn tile_pos_of(tile: (u32, u32)) -> TilePos {
+    TilePos {
+        x: tile.0,
+        y: tile.1,
+    }
+}
+
+/// A command along with the simulation tick it was applied at.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedCommand {
+    pub tick: u64,
+    pub command: PlayerCommand,
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use resources::*;
+use systems::*;
+
+use crate::AppState;
+use crate::game::SimulationStage;
+
+mod systems;
+pub mod commands;
+pub mod replay;
+pub mod resources;
+
+/**
+ * Every player input goes through `PendingCommands` instead of changing the game directly :
+ * commands are recorded against the simulation tick they are applied at, written as a replay when
+ * leaving the game, and a `ReplayPlayback` applies them again at the same ticks.
+ */
+pub struct PlayerCommandsPlugin;
+
+impl Plugin for PlayerCommandsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingCommands>();
+        app.init_resource::<CommandLog>();
+        app.add_system(reset_player_commands.in_schedule(OnEnter(AppState::Game)));
+        app.add_system(apply_simulation_commands
+            .in_set(SimulationStage::PlayerCommands)
+            .in_schedule(CoreSchedule::FixedUpdate)
+        );
+        app.add_system(apply_view_commands.in_set(OnUpdate(AppState::Game)));
+        app.add_system(save_last_replay.in_schedule(OnExit(AppState::Game)));
+        app.add_system(stop_replay
+            .run_if(resource_exists::<ReplayPlayback>())
+            .in_set(OnUpdate(AppState::MainMenu))
+        );
+    }
+}
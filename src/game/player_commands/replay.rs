@@ -0,0 +1,66 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::game::resources::CurrentLevel;
+use crate::game::save::file::FileVersion;
+
+use super::commands::RecordedCommand;
+
+/// Bumped whenever the replay format or the simulation changes in a way old replays can't be played back.
+pub const REPLAY_VERSION: u32 = 4;
+
+/// Where the replay of the last game is written when leaving it.
+pub const LAST_REPLAY_PATH: &str = "replays/last.replay.ron";
+
+/// Everything needed to play a game again : its level and the commands of the player.
+#[derive(Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub map: String,
+    pub waves: String,
+    pub starting_gold: u32,
+    pub starting_lives: u32,
+    pub commands: Vec<RecordedCommand>,
+}
+
+impl Replay {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = fs::read(path)
+            .map_err(|e| anyhow::anyhow!("Could not read replay {}: {e}", path.display()))?;
+        // the version is checked first, an older file may not have the fields of this one
+        let file_version: FileVersion = ron::de::from_bytes(&bytes)
+            .map_err(|e| anyhow::anyhow!("Could not load replay {}: {e}", path.display()))?;
+        if file_version.version != REPLAY_VERSION {
+            return Err(anyhow::anyhow!(
+                "Replay {} has version {} but only version {} is supported",
+                path.display(), file_version.version, REPLAY_VERSION,
+            ));
+        }
+        let replay: Replay = ron::de::from_bytes(&bytes)
+            .map_err(|e| anyhow::anyhow!("Could not load replay {}: {e}", path.display()))?;
+        Ok(replay)
+    }
+
+    pub fn level(&self) -> CurrentLevel {
+        CurrentLevel {
+            map: self.map.clone(),
+            waves: self.waves.clone(),
+            starting_gold: self.starting_gold,
+            starting_lives: self.starting_lives,
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, text)?;
+        Ok(())
+    }
+}
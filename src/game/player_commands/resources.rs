@@ -0,0 +1,74 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use super::commands::{PlayerCommand, RecordedCommand};
+use super::replay::Replay;
+
+/// Commands issued by the player and not applied yet.
+#[derive(Resource, Default)]
+pub struct PendingCommands {
+    pub commands: Vec<PlayerCommand>,
+}
+
+impl PendingCommands {
+    pub fn push(&mut self, command: PlayerCommand) {
+        self.commands.push(command);
+    }
+}
+
+/// Every command applied since the start of the game, saved as a replay when leaving it.
+#[derive(Resource, Default)]
+pub struct CommandLog {
+    pub commands: Vec<RecordedCommand>,
+    /// A game loaded from a save did not start at tick 0, it can't be replayed.
+    pub loaded_from_save: bool,
+}
+
+/**
+ * Present when the game is a replay : the player's own commands are ignored
+ * and the recorded ones are applied again at their tick.
+ */
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    pub simulation_commands: VecDeque<RecordedCommand>,
+    pub view_commands: VecDeque<RecordedCommand>,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        let mut playback = Self {
+            replay,
+            simulation_commands: VecDeque::new(),
+            view_commands: VecDeque::new(),
+        };
+        playback.rewind();
+        playback
+    }
+
+    /// Starts the replay over, e.g. when the game is restarted.
+    pub fn rewind(&mut self) {
+        let (simulation_commands, view_commands) = self.replay.commands.iter()
+            .cloned()
+            .partition(|recorded| recorded.command.affects_simulation());
+        self.simulation_commands = simulation_commands;
+        self.view_commands = view_commands;
+    }
+
+    /// Removes and returns the commands of the given kind recorded up to `tick`.
+    pub fn take_until(&mut self, tick: u64, simulation: bool) -> Vec<PlayerCommand> {
+        let queue = if simulation {
+            &mut self.simulation_commands
+        } else {
+            &mut self.view_commands
+        };
+        let mut commands = Vec::new();
+        while queue.front().is_some_and(|recorded| recorded.tick <= tick) {
+            if let Some(recorded) = queue.pop_front() {
+                commands.push(recorded.command);
+            }
+        }
+        commands
+    }
+}
@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+
+use crate::game::GameState;
+use crate::game::resources::{CurrentLevel, SimulationClock};
+use crate::game::tower::components::Tower;
+use crate::game::tower::events::{BuildTowerEvent, SellTowerEvent, UpgradeTowerEvent};
+use crate::game::tower::targeting::TargetingMode;
+
+use super::commands::*;
+use super::replay::{LAST_REPLAY_PATH, Replay, REPLAY_VERSION};
+use super::resources::*;
+
+pub fn reset_player_commands(
+    mut pending_commands: ResMut<PendingCommands>,
+    mut command_log: ResMut<CommandLog>,
+    replay_playback: Option<ResMut<ReplayPlayback>>,
+) {
+    *pending_commands = PendingCommands::default();
+    *command_log = CommandLog::default();
+    if let Some(mut replay_playback) = replay_playback {
+        replay_playback.rewind();
+    }
+}
+
+/**
+ * Takes the commands of the given kind out of the pending ones, or out of the replay when there is one :
+ * while replaying, the player can still pause or change the speed but not change the game.
+ */
+fn take_commands(
+    pending_commands: &mut PendingCommands,
+    replay_playback: Option<&mut ReplayPlayback>,
+    tick: u64,
+    simulation: bool,
+) -> Vec<PlayerCommand> {
+    let (taken, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut pending_commands.commands)
+        .into_iter()
+        .partition(|command| command.affects_simulation() == simulation);
+    pending_commands.commands = kept;
+    match replay_playback {
+        Some(replay_playback) if simulation => replay_playback.take_until(tick, true),
+        Some(replay_playback) => {
+            let mut commands = replay_playback.take_until(tick, false);
+            commands.extend(taken);
+            commands
+        }
+        None => taken,
+    }
+}
+
+/**
+ * Applies the commands changing the game at the start of a step and records them with its tick,
+ * so replaying them at the same ticks gives the same game.
+ */
+pub fn apply_simulation_commands(
+    mut pending_commands: ResMut<PendingCommands>,
+    mut command_log: ResMut<CommandLog>,
+    mut replay_playback: Option<ResMut<ReplayPlayback>>,
+    simulation_clock: Res<SimulationClock>,
+    mut tower_query: Query<(Entity, &Tower, &mut TargetingMode)>,
+    mut build_tower_event_writer: EventWriter<BuildTowerEvent>,
+    mut upgrade_tower_event_writer: EventWriter<UpgradeTowerEvent>,
+    mut sell_tower_event_writer: EventWriter<SellTowerEvent>,
+) {
+    let commands = take_commands(
+        &mut pending_commands,
+        replay_playback.as_deref_mut(),
+        simulation_clock.tick,
+        true,
+    );
+    for command in commands {
+        match &command {
+            PlayerCommand::BuildTower { tower_type, tile } => {
+                build_tower_event_writer.send(BuildTowerEvent {
+                    tower_type: *tower_type,
+                    tile_pos: tile_pos_of(*tile),
+                });
+            }
+            PlayerCommand::UpgradeTower { tile } => {
+                for (tower_entity, tower, _) in tower_query.iter() {
+                    if tile_of(&tower.tile_pos) == *tile {
+                        upgrade_tower_event_writer.send(UpgradeTowerEvent {
+                            tower: tower_entity,
+                        });
+                    }
+                }
+            }
+            PlayerCommand::SellTower { tile } => {
+                for (tower_entity, tower, _) in tower_query.iter() {
+                    if tile_of(&tower.tile_pos) == *tile {
+                        sell_tower_event_writer.send(SellTowerEvent {
+                            tower: tower_entity,
+                        });
+                    }
+                }
+            }
+            PlayerCommand::SetTargeting { tile, mode } => {
+                for (_, tower, mut targeting_mode) in tower_query.iter_mut() {
+                    if tile_of(&tower.tile_pos) == *tile {
+                        *targeting_mode = *mode;
+                    }
+                }
+            }
+            _ => continue,
+        }
+        command_log.commands.push(RecordedCommand {
+            tick: simulation_clock.tick,
+            command,
+        });
+    }
+}
+
+/**
+ * Applies the commands only changing how the game is watched as soon as they are issued,
+ * they must work while the simulation is paused.
+ */
+pub fn apply_view_commands(
+    mut pending_commands: ResMut<PendingCommands>,
+    mut command_log: ResMut<CommandLog>,
+    mut replay_playback: Option<ResMut<ReplayPlayback>>,
+    mut simulation_clock: ResMut<SimulationClock>,
+    mut game_next_state: ResMut<NextState<GameState>>,
+) {
+    let commands = take_commands(
+        &mut pending_commands,
+        replay_playback.as_deref_mut(),
+        simulation_clock.tick,
+        false,
+    );
+    for command in commands {
+        match &command {
+            PlayerCommand::SetSpeed { speed } => {
+                simulation_clock.speed = *speed;
+            }
+            PlayerCommand::Pause => {
+                game_next_state.set(GameState::Paused);
+            }
+            PlayerCommand::Resume => {
+                game_next_state.set(GameState::Playing);
+            }
+            _ => continue,
+        }
+        if replay_playback.is_none() {
+            command_log.commands.push(RecordedCommand {
+                tick: simulation_clock.tick,
+                command,
+            });
+        }
+    }
+}
+
+/**
+ * Leaving a game writes its replay, unless it was a replay itself or loaded from a save.
+ */
+pub fn save_last_replay(
+    command_log: Res<CommandLog>,
+    current_level: Res<CurrentLevel>,
+    replay_playback: Option<Res<ReplayPlayback>>,
+) {
+    if replay_playback.is_some() || command_log.loaded_from_save {
+        return;
+    }
+    let replay = Replay {
+        version: REPLAY_VERSION,
+        map: current_level.map.clone(),
+        waves: current_level.waves.clone(),
+        starting_gold: current_level.starting_gold,
+        starting_lives: current_level.starting_lives,
+        commands: command_log.commands.clone(),
+    };
+    match replay.save(LAST_REPLAY_PATH) {
+        Ok(()) => info!("Replay written to {}", LAST_REPLAY_PATH),
+        Err(e) => error!("Could not write replay {}: {e}", LAST_REPLAY_PATH),
+    }
+}
+
+/**
+ * Going back to the main menu ends the replay, the next game is played again.
+ */
+pub fn stop_replay(mut commands: Commands) {
+    commands.remove_resource::<ReplayPlayback>();
+}
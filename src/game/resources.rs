@@ -1,16 +1,21 @@
 use std::fmt::Debug;
+use std::time::Duration;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum TowerType {
     Arrow,
     Bomb,
+    Frost,
 }
+
 impl Debug for TowerType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             TowerType::Arrow => write!(f, "Arrow"),
             TowerType::Bomb => write!(f, "Bomb"),
+            TowerType::Frost => write!(f, "Frost"),
         }
     }
 }
@@ -27,3 +32,107 @@ impl Default for BuildTower {
         }
     }
 }
+
+/// The level being played, picked on the level select screen. Paths are relative to the assets folder.
+#[derive(Resource, Clone)]
+pub struct CurrentLevel {
+    pub map: String,
+    pub waves: String,
+    pub starting_gold: u32,
+    pub starting_lives: u32,
+}
+
+impl Default for CurrentLevel {
+    fn default() -> Self {
+        Self {
+            map: "tiled/map_1.tmx".to_string(),
+            waves: "waves/map_1.waves.ron".to_string(),
+            starting_gold: default_starting_gold(),
+            starting_lives: default_starting_lives(),
+        }
+    }
+}
+
+/// Gold at the start of a level which does not set it.
+pub fn default_starting_gold() -> u32 {
+    Gold::default().current
+}
+
+pub fn default_starting_lives() -> u32 {
+    PlayerLives::default().current
+}
+
+#[derive(Resource)]
+pub struct PlayerLives {
+    pub current: u32,
+}
+
+impl Default for PlayerLives {
+    fn default() -> Self {
+        Self {
+            current: 20,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct Gold {
+    pub current: u32,
+}
+
+impl Default for Gold {
+    fn default() -> Self {
+        Self {
+            current: 150,
+        }
+    }
+}
+
+/// Speeds the player can pick for the simulation.
+pub const GAME_SPEEDS: [f32; 3] = [1.0, 2.0, 4.0];
+
+/**
+ * Time as seen by the simulation, advanced once per fixed step.
+ * Gameplay systems read this instead of `Time` : a faster game speed runs more steps per frame.
+ */
+#[derive(Resource)]
+pub struct SimulationClock {
+    pub speed: f32,
+    /// Number of simulation steps since the start of the game.
+    pub tick: u64,
+    delta: Duration,
+}
+
+impl Default for SimulationClock {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            tick: 0,
+            delta: Duration::ZERO,
+        }
+    }
+}
+
+impl SimulationClock {
+    pub fn tick(&mut self, step: Duration) {
+        self.tick += 1;
+        self.delta = step;
+    }
+
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    pub fn delta_seconds(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+}
+
+/// What happened during the current game, shown on the game over screen.
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+pub struct GameStats {
+    pub victory: bool,
+    pub waves_survived: usize,
+    pub enemies_killed: u32,
+    pub gold_earned: u32,
+}
@@ -0,0 +1,2 @@
+/// Writes the current game to the save slot.
+pub struct SaveGameEvent;
@@ -0,0 +1,145 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Result;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::creep::effects::StatusEffectKind;
+use crate::game::creep::resources::WavePhase;
+use crate::game::resources::{CurrentLevel, GameStats, TowerType};
+use crate::game::tower::targeting::TargetingMode;
+
+/// Bumped whenever the save format changes, older saves are refused instead of loaded wrong.
+pub const SAVE_VERSION: u32 = 3;
+
+/// The single save slot, written from the pause menu and loaded from the main menu.
+pub const SAVE_PATH: &str = "saves/quicksave.save.ron";
+
+/// Only the version of a versioned file, read before the rest to refuse other versions with a clear error.
+#[derive(Deserialize)]
+pub struct FileVersion {
+    pub version: u32,
+}
+
+/**
+ * A game in progress, as written to a `*.save.ron` file.
+ * Projectiles in flight are not saved : a loaded game starts with none.
+ */
+#[derive(Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    pub map: String,
+    pub waves: String,
+    pub starting_gold: u32,
+    pub starting_lives: u32,
+    pub tick: u64,
+    pub gold: u32,
+    pub lives: u32,
+    pub stats: GameStats,
+    pub wave: SavedWaveState,
+    pub towers: Vec<SavedTower>,
+    pub creeps: Vec<SavedCreep>,
+}
+
+impl SaveFile {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = fs::read(path)
+            .map_err(|e| anyhow::anyhow!("Could not read save {}: {e}", path.display()))?;
+        // the version is checked first, an older file may not have the fields of this one
+        let file_version: FileVersion = ron::de::from_bytes(&bytes)
+            .map_err(|e| anyhow::anyhow!("Could not load save {}: {e}", path.display()))?;
+        if file_version.version != SAVE_VERSION {
+            return Err(anyhow::anyhow!(
+                "Save {} has version {} but only version {} is supported",
+                path.display(), file_version.version, SAVE_VERSION,
+            ));
+        }
+        let save: SaveFile = ron::de::from_bytes(&bytes)
+            .map_err(|e| anyhow::anyhow!("Could not load save {}: {e}", path.display()))?;
+        Ok(save)
+    }
+
+    pub fn level(&self) -> CurrentLevel {
+        CurrentLevel {
+            map: self.map.clone(),
+            waves: self.waves.clone(),
+            starting_gold: self.starting_gold,
+            starting_lives: self.starting_lives,
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, text)?;
+        Ok(())
+    }
+}
+
+/// Progress of a `Timer`, in seconds.
+#[derive(Serialize, Deserialize)]
+pub struct SavedTimer {
+    pub duration: f32,
+    pub elapsed: f32,
+}
+
+impl SavedTimer {
+    pub fn new(timer: &Timer) -> Self {
+        Self {
+            duration: timer.duration().as_secs_f32(),
+            elapsed: timer.elapsed_secs(),
+        }
+    }
+
+    pub fn to_timer(&self, mode: TimerMode) -> Timer {
+        let mut timer = Timer::from_seconds(self.duration, mode);
+        timer.set_elapsed(Duration::from_secs_f32(self.elapsed));
+        timer
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedWaveState {
+    pub phase: WavePhase,
+    pub wave_index: usize,
+    pub group_index: usize,
+    pub spawned_in_group: u32,
+    pub timer: SavedTimer,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedTower {
+    pub tower_type: TowerType,
+    pub tile: (u32, u32),
+    pub level: usize,
+    pub targeting: TargetingMode,
+    /// Seconds before the next shot.
+    pub cooldown_remaining: f32,
+    pub kills: u32,
+    pub damage_dealt: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedCreep {
+    pub kind: String,
+    pub health: i32,
+    /// Name of the followed path.
+    pub path: String,
+    pub waypoint_index: i32,
+    /// World position.
+    pub position: (f32, f32),
+    pub status_effects: Vec<SavedStatusEffect>,
+    pub pending_damage: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedStatusEffect {
+    pub kind: StatusEffectKind,
+    pub timer: SavedTimer,
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+use events::*;
+use resources::*;
+use systems::*;
+
+use crate::AppState;
+use crate::game::is_level_loaded;
+
+mod systems;
+pub mod events;
+pub mod file;
+pub mod resources;
+
+/**
+ * Saves the game in progress to a versioned file, and restores it once its level is loaded :
+ * the simulation does not run while a `PendingLoad` is waiting.
+ */
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SaveGameEvent>();
+        app.add_system(save_game.in_set(OnUpdate(AppState::Game)));
+        app.add_systems(
+            (
+                restore_saved_game
+                    .run_if(resource_exists::<PendingLoad>())
+                    .run_if(is_level_loaded),
+                // the restored towers must exist to get their cooldown back
+                apply_system_buffers,
+                restore_tower_cooldowns
+                    .run_if(resource_exists::<PendingLoad>())
+                    .run_if(is_level_loaded),
+            )
+                .chain()
+                .in_set(OnUpdate(AppState::Game))
+        );
+    }
+}
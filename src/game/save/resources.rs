@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+use super::file::SaveFile;
+
+/// A save picked from the main menu, restored once its level is loaded.
+#[derive(Resource)]
+pub struct PendingLoad(pub SaveFile);
@@ -0,0 +1,216 @@
+use std::time::Duration;
+
+use bevy::log;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::game::components::Interpolated;
+use crate::game::creep::components::{Dying, Enemy, Health, WaypointFollower};
+use crate::game::creep::effects::{StatusEffect, StatusEffects};
+use crate::game::creep::kinds::EnemyKinds;
+use crate::game::creep::resources::{EnemyRegistry, WaveState};
+use crate::game::creep::spawn_enemy_entity;
+use crate::game::player_commands::commands::{tile_of, tile_pos_of};
+use crate::game::player_commands::resources::CommandLog;
+use crate::game::resources::{CurrentLevel, GameStats, Gold, PlayerLives, SimulationClock};
+use crate::game::tower::components::{ProjectileThrower, Splasher, Tower, TowerLevel, TowerStats};
+use crate::game::tower::resources::TowerCatalog;
+use crate::game::tower::spawn_tower;
+use crate::game::tower::targeting::TargetingMode;
+
+use super::events::SaveGameEvent;
+use super::file::*;
+use super::resources::PendingLoad;
+
+pub fn save_game(
+    mut save_game_event_reader: EventReader<SaveGameEvent>,
+    current_level: Res<CurrentLevel>,
+    simulation_clock: Res<SimulationClock>,
+    gold: Res<Gold>,
+    player_lives: Res<PlayerLives>,
+    game_stats: Res<GameStats>,
+    wave_state: Option<Res<WaveState>>,
+    tower_query: Query<(&Tower, &TowerLevel, &TargetingMode, &TowerStats, Option<&ProjectileThrower>, Option<&Splasher>)>,
+    creep_query: Query<(&Enemy, &Health, &WaypointFollower, &Interpolated, &StatusEffects), Without<Dying>>,
+) {
+    if save_game_event_reader.iter().count() == 0 {
+        return;
+    }
+    let Some(wave_state) = wave_state else {
+        return;
+    };
+
+    let towers = tower_query.iter()
+        .map(|(tower, tower_level, targeting_mode, tower_stats, projectile_thrower, splasher)| {
+            let cooldown = projectile_thrower.map(|projectile_thrower| &projectile_thrower.cooldown)
+                .or(splasher.map(|splasher| &splasher.cooldown));
+            SavedTower {
+                tower_type: tower.tower_type,
+                tile: tile_of(&tower.tile_pos),
+                level: tower_level.0,
+                targeting: *targeting_mode,
+                cooldown_remaining: cooldown.map_or(0.0, |cooldown| cooldown.remaining_secs()),
+                kills: tower_stats.kills,
+                damage_dealt: tower_stats.damage_dealt,
+            }
+        })
+        .collect();
+    let creeps = creep_query.iter()
+        .map(|(enemy, health, waypoint_follower, interpolated, status_effects)| SavedCreep {
+            kind: enemy.kind.clone(),
+            health: health.current,
+            path: waypoint_follower.path.clone(),
+            waypoint_index: waypoint_follower.index,
+            // the transform is interpolated for rendering, the simulated position is the current one
+            position: (interpolated.current.x, interpolated.current.y),
+            status_effects: status_effects.effects.iter()
+                .map(|effect| SavedStatusEffect {
+                    kind: effect.kind,
+                    timer: SavedTimer::new(&effect.timer),
+                })
+                .collect(),
+            pending_damage: status_effects.pending_damage,
+        })
+        .collect();
+
+    let save = SaveFile {
+        version: SAVE_VERSION,
+        map: current_level.map.clone(),
+        waves: current_level.waves.clone(),
+        starting_gold: current_level.starting_gold,
+        starting_lives: current_level.starting_lives,
+        tick: simulation_clock.tick,
+        gold: gold.current,
+        lives: player_lives.current,
+        stats: game_stats.clone(),
+        wave: SavedWaveState {
+            phase: wave_state.phase,
+            wave_index: wave_state.wave_index,
+            group_index: wave_state.group_index,
+            spawned_in_group: wave_state.spawned_in_group,
+            timer: SavedTimer::new(&wave_state.timer),
+        },
+        towers,
+        creeps,
+    };
+    match save.save(SAVE_PATH) {
+        Ok(()) => log::info!("Game saved to {}", SAVE_PATH),
+        Err(e) => log::error!("Could not write save {}: {e}", SAVE_PATH),
+    }
+}
+
+/**
+ * Puts the saved game back on top of the freshly loaded level : resources, wave progress, towers and creeps.
+ */
+pub fn restore_saved_game(
+    mut commands: Commands,
+    pending_load: Res<PendingLoad>,
+    tilemap_query: Query<(&TileStorage, &TilemapGridSize, &TilemapType, &GlobalTransform)>,
+    mut wave_state: ResMut<WaveState>,
+    enemy_registry: Res<EnemyRegistry>,
+    enemy_kinds: Res<Assets<EnemyKinds>>,
+    asset_server: Res<AssetServer>,
+    tower_catalog: Res<TowerCatalog>,
+    mut gold: ResMut<Gold>,
+    mut player_lives: ResMut<PlayerLives>,
+    mut game_stats: ResMut<GameStats>,
+    mut simulation_clock: ResMut<SimulationClock>,
+    mut command_log: ResMut<CommandLog>,
+) {
+    let save = &pending_load.0;
+    let Ok((tile_storage, tilemap_grid_size, tilemap_type, tilemap_transform)) = tilemap_query.get_single() else {
+        return;
+    };
+    let Some(enemy_kinds) = enemy_kinds.get(&enemy_registry.kinds) else {
+        return;
+    };
+
+    simulation_clock.tick = save.tick;
+    gold.current = save.gold;
+    player_lives.current = save.lives;
+    *game_stats = save.stats.clone();
+    command_log.loaded_from_save = true;
+
+    wave_state.phase = save.wave.phase;
+    wave_state.wave_index = save.wave.wave_index;
+    wave_state.group_index = save.wave.group_index;
+    wave_state.spawned_in_group = save.wave.spawned_in_group;
+    wave_state.timer = save.wave.timer.to_timer(TimerMode::Once);
+
+    for saved_tower in save.towers.iter() {
+        let tile_pos = tile_pos_of(saved_tower.tile);
+        let Some(tile_entity) = tile_storage.get(&tile_pos) else {
+            log::warn!("Skipped saved tower on missing tile {:?}.", tile_pos);
+            continue;
+        };
+        let Some(stats) = tower_catalog.level(saved_tower.tower_type, saved_tower.level) else {
+            log::warn!("Skipped saved {:?} tower of unknown level {}.", saved_tower.tower_type, saved_tower.level);
+            continue;
+        };
+        let tile_world_pos = tile_pos.center_in_world(tilemap_grid_size, tilemap_type);
+        let translation = Vec3::from((tile_world_pos, 10.0)) + tilemap_transform.translation();
+        let tower_id = spawn_tower(&mut commands, &asset_server, stats, saved_tower.tower_type, saved_tower.level, tile_entity, tile_pos, translation);
+        commands.entity(tower_id).insert((
+            saved_tower.targeting,
+            TowerStats {
+                kills: saved_tower.kills,
+                damage_dealt: saved_tower.damage_dealt,
+            },
+        ));
+    }
+
+    for saved_creep in save.creeps.iter() {
+        let Some(enemy_kind) = enemy_kinds.kinds.get(&saved_creep.kind) else {
+            log::warn!("Skipped saved creep of unknown kind {}.", saved_creep.kind);
+            continue;
+        };
+        let translation = Vec3::new(saved_creep.position.0, saved_creep.position.1, 10.0);
+        let creep_id = spawn_enemy_entity(&mut commands, &asset_server, &saved_creep.kind, enemy_kind, &saved_creep.path, translation);
+        commands.entity(creep_id).insert((
+            Health {
+                current: saved_creep.health,
+                max: enemy_kind.health,
+            },
+            WaypointFollower {
+                path: saved_creep.path.clone(),
+                index: saved_creep.waypoint_index,
+            },
+            StatusEffects {
+                effects: saved_creep.status_effects.iter()
+                    .map(|saved_effect| StatusEffect {
+                        kind: saved_effect.kind,
+                        timer: saved_effect.timer.to_timer(TimerMode::Once),
+                    })
+                    .collect(),
+                pending_damage: saved_creep.pending_damage,
+            },
+        ));
+    }
+
+    log::info!("Restored {} towers and {} creeps at tick {}", save.towers.len(), save.creeps.len(), save.tick);
+}
+
+/**
+ * Towers are spawned with a fresh cooldown, the saved one is set once they exist. This ends the load.
+ */
+pub fn restore_tower_cooldowns(
+    mut commands: Commands,
+    pending_load: Res<PendingLoad>,
+    mut tower_query: Query<(&Tower, Option<&mut ProjectileThrower>, Option<&mut Splasher>)>,
+) {
+    for saved_tower in pending_load.0.towers.iter() {
+        for (tower, projectile_thrower, splasher) in tower_query.iter_mut() {
+            if tile_of(&tower.tile_pos) != saved_tower.tile {
+                continue;
+            }
+            let cooldown = match (projectile_thrower, splasher) {
+                (Some(projectile_thrower), _) => &mut projectile_thrower.into_inner().cooldown,
+                (_, Some(splasher)) => &mut splasher.into_inner().cooldown,
+                _ => continue,
+            };
+            let elapsed = (cooldown.duration().as_secs_f32() - saved_tower.cooldown_remaining).max(0.0);
+            cooldown.set_elapsed(Duration::from_secs_f32(elapsed));
+        }
+    }
+    commands.remove_resource::<PendingLoad>();
+}
@@ -1,5 +1,11 @@
 use bevy::prelude::*;
 
+use crate::game::{GameState, UiState};
+use crate::game::components::{GameEntity, Interpolated};
+use crate::game::player_commands::commands::PlayerCommand;
+use crate::game::player_commands::resources::PendingCommands;
+use crate::game::resources::{BuildTower, CurrentLevel, GAME_SPEEDS, GameStats, Gold, PlayerLives, SimulationClock};
+
 const CAMERA_SPEED: f32 = 1000.0;
 
 pub fn move_camera(
@@ -22,3 +28,144 @@ pub fn move_camera(
     }
     camera_query.single_mut().translation += direction * CAMERA_SPEED * time.delta_seconds();
 }
+
+/**
+ * Escape or P toggles the pause menu.
+ */
+pub fn toggle_pause(
+    keyboard: Res<Input<KeyCode>>,
+    game_state: Res<State<GameState>>,
+    mut pending_commands: ResMut<PendingCommands>,
+    mut ui_next_state: ResMut<NextState<UiState>>,
+) {
+    if !keyboard.any_just_pressed([KeyCode::Escape, KeyCode::P]) {
+        return;
+    }
+    if game_state.0 == GameState::Paused {
+        pending_commands.push(PlayerCommand::Resume);
+    } else {
+        pending_commands.push(PlayerCommand::Pause);
+    }
+    ui_next_state.set(UiState::Nothing);
+}
+
+pub fn tick_simulation_clock(
+    mut simulation_clock: ResMut<SimulationClock>,
+    fixed_time: Res<FixedTime>,
+) {
+    simulation_clock.tick(fixed_time.period);
+}
+
+/**
+ * The game speed does not stretch the steps, it runs more of them : x2 feeds twice the frame time to the fixed clock.
+ */
+pub fn speed_up_fixed_time(
+    mut fixed_time: ResMut<FixedTime>,
+    simulation_clock: Res<SimulationClock>,
+    time: Res<Time>,
+) {
+    if simulation_clock.speed > 1.0 {
+        fixed_time.tick(time.delta().mul_f32(simulation_clock.speed - 1.0));
+    }
+}
+
+/**
+ * Puts back the simulated translations before the simulation steps, so they never see interpolated ones.
+ */
+pub fn restore_simulated_translations(
+    mut interpolated_query: Query<(&Interpolated, &mut Transform)>,
+) {
+    for (interpolated, mut transform) in interpolated_query.iter_mut() {
+        transform.translation = interpolated.current;
+    }
+}
+
+pub fn store_previous_translations(
+    mut interpolated_query: Query<(&mut Interpolated, &Transform)>,
+) {
+    for (mut interpolated, transform) in interpolated_query.iter_mut() {
+        interpolated.previous = transform.translation;
+    }
+}
+
+pub fn store_current_translations(
+    mut interpolated_query: Query<(&mut Interpolated, &Transform)>,
+) {
+    for (mut interpolated, transform) in interpolated_query.iter_mut() {
+        interpolated.current = transform.translation;
+    }
+}
+
+/**
+ * Draws moving entities where they are between the last two steps, so the motion stays smooth
+ * when the framerate and the simulation rate differ.
+ */
+pub fn interpolate_translations(
+    mut interpolated_query: Query<(&Interpolated, &mut Transform)>,
+    fixed_time: Res<FixedTime>,
+) {
+    let alpha = (fixed_time.accumulated().as_secs_f32() / fixed_time.period.as_secs_f32()).clamp(0.0, 1.0);
+    for (interpolated, mut transform) in interpolated_query.iter_mut() {
+        transform.translation = interpolated.previous.lerp(interpolated.current, alpha);
+    }
+}
+
+/**
+ * 1, 2 and 4 switch the game speed.
+ */
+pub fn change_game_speed(
+    keyboard: Res<Input<KeyCode>>,
+    simulation_clock: Res<SimulationClock>,
+    mut pending_commands: ResMut<PendingCommands>,
+) {
+    let keys = [KeyCode::Key1, KeyCode::Key2, KeyCode::Key4];
+    for (key, speed) in keys.iter().zip(GAME_SPEEDS) {
+        if keyboard.just_pressed(*key) && simulation_clock.speed != speed {
+            pending_commands.push(PlayerCommand::SetSpeed {
+                speed,
+            });
+        }
+    }
+}
+
+/**
+ * Every game starts from scratch, e.g. when retrying after a game over, with the gold and lives of its level.
+ */
+pub fn reset_game_resources(
+    current_level: Res<CurrentLevel>,
+    mut build_tower: ResMut<BuildTower>,
+    mut player_lives: ResMut<PlayerLives>,
+    mut gold: ResMut<Gold>,
+    mut game_stats: ResMut<GameStats>,
+    mut simulation_clock: ResMut<SimulationClock>,
+) {
+    *build_tower = BuildTower::default();
+    *player_lives = PlayerLives {
+        current: current_level.starting_lives,
+    };
+    *gold = Gold {
+        current: current_level.starting_gold,
+    };
+    *game_stats = GameStats::default();
+    *simulation_clock = SimulationClock::default();
+}
+
+/**
+ * Leaving the game despawns everything spawned for it, so the next game starts from an empty world.
+ */
+pub fn despawn_game_entities(
+    mut commands: Commands,
+    game_entity_query: Query<Entity, With<GameEntity>>,
+) {
+    for game_entity in game_entity_query.iter() {
+        commands.entity(game_entity).despawn_recursive();
+    }
+}
+
+pub fn reset_game_states(
+    mut game_next_state: ResMut<NextState<GameState>>,
+    mut ui_next_state: ResMut<NextState<UiState>>,
+) {
+    game_next_state.set(GameState::Playing);
+    ui_next_state.set(UiState::Nothing);
+}
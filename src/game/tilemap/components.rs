@@ -4,10 +4,48 @@ use crate::game::resources::TowerType;
 #[derive(Component)]
 pub struct SelectedForBuild {}
 
+/// A tower stands on this tile.
 #[derive(Component)]
-pub struct BuiltTile {}
+pub struct BuiltTile {
+    pub tower: Entity,
+}
 
 #[derive(Component)]
 pub struct BuildZone {
     pub rect: Rect,
 }
+
+/**
+ * Properties of a tile, set per tile in the Tiled tileset.
+ * A tile can be built on when it is `buildable` or inside a `BuildZone`, and neither `path` nor `blocked`.
+ */
+#[derive(Component, Reflect, Default, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct TileProperties {
+    pub buildable: bool,
+    /// Enemies walk on this tile.
+    pub path: bool,
+    /// Nothing can be built here, even inside a build zone, e.g. trees or the edges of the path.
+    pub blocked: bool,
+    /// Height of the ground, shown in the world inspector.
+    pub elevation: i32,
+}
+
+impl TileProperties {
+    pub fn from_tiled(properties: &tiled::Properties) -> Self {
+        let flag = |name: &str| matches!(properties.get(name), Some(tiled::PropertyValue::BoolValue(true)));
+        Self {
+            buildable: flag("buildable"),
+            path: flag("path"),
+            blocked: flag("blocked"),
+            elevation: match properties.get("elevation") {
+                Some(tiled::PropertyValue::IntValue(elevation)) => *elevation,
+                _ => 0,
+            },
+        }
+    }
+
+    pub fn can_build(&self, in_build_zone: bool) -> bool {
+        !self.path && !self.blocked && (self.buildable || in_build_zone)
+    }
+}
@@ -0,0 +1,2 @@
+/// A map was edited on disk and rebuilt while the game runs : tiles and objects are new entities.
+pub struct MapReloadedEvent;
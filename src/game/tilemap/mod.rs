@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
 
 use components::*;
+use events::*;
 use resources::*;
 use systems::*;
 use crate::AppState;
@@ -9,20 +10,24 @@ use crate::game::{GameState, UiState};
 
 mod systems;
 pub mod components;
-mod resources;
+pub mod resources;
+pub mod events;
+pub mod validation;
 mod tiled;
+mod tilesets;
+
+pub use tiled::MapProcessingSet;
 
 pub struct TilemapPlugin;
 
 impl Plugin for TilemapPlugin {
     fn build(&self, app: &mut App) {
         app
-            .init_resource::<CursorPos>();
-        ;
-        app
-            .add_plugin(bevy_ecs_tilemap::TilemapPlugin)
-            .add_plugin(tiled::TiledMapPlugin)
+            .init_resource::<CursorPos>()
+            .register_type::<TileProperties>()
+            .add_event::<MapReloadedEvent>()
         ;
+        app.add_plugin(tiled::TiledMapPlugin);
         app
             .add_system(setup_map.in_schedule(OnEnter(AppState::Game)))
         ;
@@ -48,3 +53,19 @@ pub fn can_build(
 ) -> bool {
     return game_state.0 == GameState::Building && ui_state.0 == UiState::Nothing;
 }
+
+/**
+ * Whether a tower can stand on a tile, from its properties and the build zones of the map.
+ */
+pub fn is_buildable_tile(
+    tile_pos: &TilePos,
+    tile_properties: Option<&TileProperties>,
+    grid_size: &TilemapGridSize,
+    map_type: &TilemapType,
+    tile_size: &TilemapTileSize,
+    build_zones: &Query<&BuildZone>,
+) -> bool {
+    let tile_center = tile_pos.center_in_world(grid_size, map_type) + Vec2::new(tile_size.x / 2.0, tile_size.y / 2.0);
+    let in_build_zone = build_zones.iter().any(|build_zone| build_zone.rect.contains(tile_center));
+    tile_properties.copied().unwrap_or_default().can_build(in_build_zone)
+}
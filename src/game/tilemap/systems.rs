@@ -1,10 +1,12 @@
 use bevy::math::Vec4Swizzles;
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
-use crate::game::resources::TowerType;
+use crate::game::components::GameEntity;
+use crate::game::resources::{CurrentLevel, TowerType};
 use super::tiled::*;
 use super::components::*;
 use super::resources::*;
+use super::is_buildable_tile;
 
 pub fn update_mouse_pos_display(
     window: Query<&Window>,
@@ -24,13 +26,17 @@ pub fn update_mouse_pos_display(
 pub fn setup_map(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    current_level: Res<CurrentLevel>,
 ) {
-    let map_handle: Handle<TiledMap> = asset_server.load("tiled/map_1.tmx");
+    let map_handle: Handle<TiledMap> = asset_server.load(current_level.map.as_str());
 
-    commands.spawn(TiledMapBundle {
-        tiled_map: map_handle,
-        ..default()
-    });
+    commands.spawn((
+        TiledMapBundle {
+            tiled_map: map_handle,
+            ..default()
+        },
+        GameEntity,
+    ));
 }
 
 fn remove_selected_for_build(
@@ -65,6 +71,7 @@ pub fn select_build_zone(
     selected_for_build_q: Query<Entity, With<SelectedForBuild>>,
     build_zones_q: Query<&BuildZone>,
     built_tiles_q: Query<&BuiltTile>,
+    tile_properties_q: Query<&TileProperties>,
     tilemap_q: Query<(
         &TilemapSize,
         &TilemapGridSize,
@@ -95,24 +102,14 @@ pub fn select_build_zone(
             continue;
         };
 
-        // check if tile is in build zone
-        let tile_center_pos_world = tile_pos.center_in_world(&grid_size, &map_type) + Vec2::new(tile_size.x / 2.0, tile_size.y / 2.0);
-        let mut in_build_zone = false;
-        for build_zone in build_zones_q.iter() {
-            let mut rect = build_zone.rect;
-            if rect.contains(tile_center_pos_world) {
-                in_build_zone = true;
-                break;
-            }
-        }
-        if !in_build_zone {
-            continue;
-        }
-
         let Some(tile_entity) = tile_storage.get(&tile_pos) else {
             continue;
         };
 
+        if !is_buildable_tile(&tile_pos, tile_properties_q.get(tile_entity).ok(), grid_size, map_type, tile_size, &build_zones_q) {
+            continue;
+        }
+
         if built_tiles_q.get(tile_entity).is_ok() {
             // a building is already on this tile
             continue;
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::io::BufReader;
 
 use anyhow::Result;
@@ -11,12 +12,17 @@ use bevy::{
 };
 use bevy_ecs_tilemap::prelude::*;
 use tiled::ObjectShape;
-use tiled::PropertyValue::IntValue;
+use tiled::PropertyValue::{BoolValue, IntValue, StringValue};
 
+use crate::game::components::GameEntity;
 use crate::game::creep::components::*;
+use crate::game::creep::path::{DEFAULT_PATH_NAME, DEFAULT_PATH_SAMPLES, Path, sample_catmull_rom};
 
 // use tiled::PropertyValue;
 use super::components::*;
+use super::events::MapReloadedEvent;
+use super::tilesets::{external_tileset_sources, inline_external_tilesets};
+use super::validation::validate_map;
 
 #[derive(Default)]
 pub struct TiledMapPlugin;
@@ -25,7 +31,13 @@ impl Plugin for TiledMapPlugin {
     fn build(&self, app: &mut App) {
         app.add_asset::<TiledMap>()
             .add_asset_loader(TiledLoader)
-            .add_system(process_loaded_maps);
+            .add_systems(
+                (
+                    process_loaded_maps,
+                    // systems reacting to a reload must see the new tiles and objects
+                    apply_system_buffers,
+                ).chain().in_set(MapProcessingSet)
+            );
     }
 }
 
@@ -41,16 +53,27 @@ pub struct TiledMap {
     pub tile_image_offsets: HashMap<(usize, tiled::TileId), u32>,
 }
 
+/// Loaded maps are turned into tiles and objects in this set, run after it to see them.
+#[derive(SystemSet, Debug, Clone, Eq, PartialEq, Hash)]
+pub struct MapProcessingSet;
+
 // Stores a list of tiled layers.
 #[derive(Component, Default)]
 pub struct TiledLayersStorage {
     pub storage: HashMap<u32, Entity>,
 }
 
+/// Entities spawned from the object layers of a map, replaced when the map is reloaded.
+#[derive(Component, Default)]
+pub struct TiledObjectsStorage {
+    pub entities: Vec<Entity>,
+}
+
 #[derive(Default, Bundle)]
 pub struct TiledMapBundle {
     pub tiled_map: Handle<TiledMap>,
     pub storage: TiledLayersStorage,
+    pub objects: TiledObjectsStorage,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
 }
@@ -73,11 +96,27 @@ impl AssetLoader for TiledLoader {
                 .parent()
                 .expect("The asset load context was empty.");
 
-            let mut loader = tiled::Loader::new();
-            let map = loader
-                .load_tmx_map_from(BufReader::new(bytes), load_context.path())
+            // Reading external tilesets from the load context also reloads the map when they change.
+            let map_dir = load_context.path().parent().unwrap_or(std::path::Path::new("")).to_path_buf();
+            let mut tilesets = HashMap::default();
+            for source in external_tileset_sources(bytes)? {
+                let tsx = load_context.read_asset_bytes(map_dir.join(&source)).await
+                    .map_err(|e| anyhow::anyhow!("Could not read the tileset {source} used by the map: {e}"))?;
+                tilesets.insert(source, tsx);
+            }
+            let tmx = inline_external_tilesets(bytes, &tilesets)?;
+            let map = tiled::Loader::new()
+                .load_tmx_map_from(BufReader::new(tmx.as_slice()), load_context.path())
                 .map_err(|e| anyhow::anyhow!("Could not load TMX map: {e}"))?;
 
+            let report = validate_map(&map);
+            if report.has_errors() {
+                return Err(anyhow::anyhow!("Invalid map {}:\n{report}", load_context.path().display()));
+            }
+            if !report.issues.is_empty() {
+                log::warn!("Map {}:\n{report}", load_context.path().display());
+            }
+
             let mut dependencies = Vec::new();
             let mut tilemap_textures = HashMap::default();
             #[cfg(not(feature = "atlas"))]
@@ -146,15 +185,83 @@ impl AssetLoader for TiledLoader {
     }
 }
 
+/// Paths, spawners and finishes read from the object layers of a map, as (name, ..., name of what they are bound to).
+#[derive(Default)]
+struct MapPaths {
+    paths: Vec<(String, Vec<Vec2>, Option<String>)>,
+    /// Waypoint points by the name of their path, with their index in it.
+    indexed_waypoints: BTreeMap<String, Vec<(i32, Vec2)>>,
+    spawners: Vec<(String, Vec2, Option<String>)>,
+    finishes: Vec<(String, Vec2)>,
+}
+
+impl MapPaths {
+    /**
+     * Spawns the paths with their finish, and the spawners with their path.
+     * A path without a `finish` property ends at the closest finish, a spawner without a `path` property
+     * uses the only path of the map.
+     */
+    fn spawn(mut self, commands: &mut Commands) -> Vec<Entity> {
+        for (name, mut waypoints) in std::mem::take(&mut self.indexed_waypoints) {
+            waypoints.sort_by_key(|(index, _)| *index);
+            self.paths.push((name, waypoints.into_iter().map(|(_, position)| position).collect(), None));
+        }
+
+        let mut entities = Vec::new();
+        let mut path_names = Vec::new();
+        for (name, waypoints, finish_name) in self.paths {
+            let end = waypoints.last().copied().unwrap_or(Vec2::ZERO);
+            let finish = match finish_name {
+                Some(finish_name) => self.finishes.iter().find(|(candidate, _)| *candidate == finish_name),
+                None => self.finishes.iter().min_by(|(_, a), (_, b)| a.distance(end).total_cmp(&b.distance(end))),
+            };
+            entities.push(commands.spawn((
+                Path::new(name.clone(), waypoints, finish.map(|(_, position)| *position)),
+                GameEntity,
+                Name::new(format!("Path ({name})")),
+            )).id());
+            path_names.push(name);
+        }
+
+        for (name, position, path) in self.spawners {
+            let path = match (path, path_names.as_slice()) {
+                (Some(path), _) => path,
+                (None, [only]) => only.clone(),
+                (None, _) => String::new(),
+            };
+            entities.push(commands.spawn((
+                EnemySpawner {
+                    name: name.clone(),
+                    position,
+                    path,
+                },
+                GameEntity,
+                Name::new(name),
+            )).id());
+        }
+        entities
+    }
+}
+
+fn string_property(properties: &tiled::Properties, name: &str) -> Option<String> {
+    match properties.get(name) {
+        Some(StringValue(value)) => Some(value.clone()),
+        _ => None,
+    }
+}
+
 pub fn process_loaded_maps(
     mut commands: Commands,
     mut map_events: EventReader<AssetEvent<TiledMap>>,
     maps: Res<Assets<TiledMap>>,
     tile_storage_query: Query<(Entity, &TileStorage)>,
-    mut map_query: Query<(&Handle<TiledMap>, &mut TiledLayersStorage)>,
+    mut map_query: Query<(&Handle<TiledMap>, &mut TiledLayersStorage, &mut TiledObjectsStorage)>,
     new_maps: Query<&Handle<TiledMap>, Added<Handle<TiledMap>>>,
+    mut map_reloaded_event_writer: EventWriter<MapReloadedEvent>,
 ) {
     let mut changed_maps = Vec::<Handle<TiledMap>>::default();
+    // maps edited while the game runs, as opposed to loaded for the first time
+    let mut modified_maps = Vec::<Handle<TiledMap>>::default();
     for event in map_events.iter() {
         match event {
             AssetEvent::Created { handle } => {
@@ -162,14 +269,16 @@ pub fn process_loaded_maps(
                 changed_maps.push(handle.clone());
             }
             AssetEvent::Modified { handle } => {
-                // log::info!("Map changed!");
-                // changed_maps.push(handle.clone());
+                log::info!("Map changed!");
+                changed_maps.push(handle.clone());
+                modified_maps.push(handle.clone());
             }
             AssetEvent::Removed { handle } => {
                 log::info!("Map removed!");
                 // if mesh was modified and removed in the same update, ignore the modification
                 // events are ordered so future modification events are ok
-                changed_maps.retain(|changed_handle| changed_handle == handle);
+                changed_maps.retain(|changed_handle| changed_handle != handle);
+                modified_maps.retain(|modified_handle| modified_handle != handle);
             }
         }
     }
@@ -178,9 +287,10 @@ pub fn process_loaded_maps(
     for new_map_handle in new_maps.iter() {
         changed_maps.push(new_map_handle.clone_weak());
     }
+    changed_maps.dedup();
 
     for changed_map in changed_maps.iter() {
-        for (map_handle, mut layer_storage) in map_query.iter_mut() {
+        for (map_handle, mut layer_storage, mut objects_storage) in map_query.iter_mut() {
             // only deal with currently changed map
             if map_handle != changed_map {
                 continue;
@@ -192,8 +302,14 @@ pub fn process_loaded_maps(
                             commands.entity(*tile).despawn_recursive()
                         }
                     }
-                    // commands.entity(*layer_entity).despawn_recursive();
+                    commands.entity(*layer_entity).despawn_recursive();
+                }
+                layer_storage.storage.clear();
+                for object_entity in objects_storage.entities.drain(..) {
+                    commands.entity(object_entity).despawn_recursive();
                 }
+                // paths and spawners refer to each other and to finishes by name, they are spawned once all objects are read
+                let mut map_paths = MapPaths::default();
 
                 // The TilemapBundle requires that all tile images come exclusively from a single
                 // tiled texture or from a Vec of independent per-tile images. Furthermore, all of
@@ -224,31 +340,57 @@ pub fn process_loaded_maps(
                         let offset_y = layer.offset_y;
 
                         if let tiled::LayerType::ObjectLayer(object_layer) = layer.layer_type() {
+                            // objects do not depend on the tileset, they are spawned once
+                            if tileset_index != 0 {
+                                continue;
+                            }
                             for object_data in object_layer.object_data() {
                                 let mapped_x = object_data.x + offset_x;
                                 let mapped_y = tiled_map.map.height as f32 * tile_size.y - (object_data.y + offset_y);
 
-                                match object_data.user_type.as_str() {
+                                let object_entity = match object_data.user_type.as_str() {
                                     "Waypoint" => {
-                                        let IntValue(index) = object_data.properties["waypoint"] else {
+                                        let Some(IntValue(index)) = object_data.properties.get("waypoint") else {
                                             log::warn!("Skipped entity waypoint because no waypoint property found.");
                                             continue;
                                         };
-                                        commands.spawn(Waypoint {
-                                            index,
-                                            position: Vec2::new(mapped_x, mapped_y),
-                                        }).insert(Name::new(object_data.name.clone()));
+                                        let path = match object_data.properties.get("path") {
+                                            Some(StringValue(path)) => path.clone(),
+                                            _ => DEFAULT_PATH_NAME.to_string(),
+                                        };
+                                        map_paths.indexed_waypoints.entry(path).or_default().push((*index, Vec2::new(mapped_x, mapped_y)));
+                                        continue;
+                                    }
+                                    "EnemyPath" => {
+                                        let ObjectShape::Polyline { points } = &object_data.shape else {
+                                            log::warn!("Skipped enemy path {} because it is not a polyline.", object_data.name);
+                                            continue;
+                                        };
+                                        let points: Vec<Vec2> = points.iter()
+                                            .map(|(x, y)| Vec2::new(mapped_x + x, mapped_y - y))
+                                            .collect();
+                                        let waypoints = match object_data.properties.get("curved") {
+                                            Some(BoolValue(true)) => {
+                                                let samples = match object_data.properties.get("samples") {
+                                                    Some(IntValue(samples)) if *samples > 0 => *samples as u32,
+                                                    _ => DEFAULT_PATH_SAMPLES,
+                                                };
+                                                sample_catmull_rom(&points, samples)
+                                            }
+                                            _ => points,
+                                        };
+                                        map_paths.paths.push((object_data.name.clone(), waypoints, string_property(&object_data.properties, "finish")));
+                                        continue;
                                     }
                                     "EnemyFinish" => {
+                                        map_paths.finishes.push((object_data.name.clone(), Vec2::new(mapped_x, mapped_y)));
                                         commands.spawn(EnemyFinish {
                                             position: Vec2::new(mapped_x, mapped_y),
-                                        }).insert(Name::new(object_data.name.clone()));
+                                        }).id()
                                     }
                                     "EnemySpawner" => {
-                                        commands.spawn(EnemySpawner {
-                                            position: Vec2::new(mapped_x, mapped_y),
-                                            timer: Timer::from_seconds(1.5, TimerMode::Repeating),
-                                        }).insert(Name::new(object_data.name.clone()));
+                                        map_paths.spawners.push((object_data.name.clone(), Vec2::new(mapped_x, mapped_y), string_property(&object_data.properties, "path")));
+                                        continue;
                                     }
                                     "BuildZone" => {
                                         let (shape_width, shape_height) = match object_data.shape {
@@ -257,10 +399,12 @@ pub fn process_loaded_maps(
                                         };
                                         commands.spawn(BuildZone {
                                             rect: Rect::new(mapped_x, mapped_y, mapped_x + shape_width, mapped_y - shape_height),
-                                        }).insert(Name::new(object_data.name.clone()));
+                                        }).id()
                                     }
-                                    _ => {}
-                                }
+                                    _ => continue,
+                                };
+                                commands.entity(object_entity).insert((GameEntity, Name::new(object_data.name.clone())));
+                                objects_storage.entities.push(object_entity);
                             }
 
                             continue;
@@ -306,7 +450,7 @@ pub fn process_loaded_maps(
                         };
 
                         let mut tile_storage = TileStorage::empty(map_size);
-                        let layer_entity = commands.spawn_empty().id();
+                        let layer_entity = commands.spawn(GameEntity).id();
 
                         for x in 0..map_size.x {
                             for y in 0..map_size.y {
@@ -333,17 +477,9 @@ pub fn process_loaded_maps(
                                         }
                                     };
 
-                                // TODO : if we want properties of tiles
-                                // let tile = match layer_tile.get_tile() {
-                                //     Some(t) => t,
-                                //     None => {
-                                //         continue;
-                                //     }
-                                // };
-                                // let tile_properties = &tile.properties;
-                                // if ! tile_properties.is_empty() {
-                                //     println!("tile properties of {:?}: {:?}", layer_tile.id(), tile_properties);
-                                // }
+                                let tile_properties = layer_tile.get_tile()
+                                    .map(|tile| TileProperties::from_tiled(&tile.properties))
+                                    .unwrap_or_default();
 
                                 let texture_index = match tilemap_texture {
                                     TilemapTexture::Single(_) => layer_tile.id(),
@@ -356,7 +492,7 @@ pub fn process_loaded_maps(
                                 };
 
                                 let tile_pos = TilePos { x, y };
-                                let tile_entity_builder = commands
+                                let mut tile_entity_builder = commands
                                     .spawn(TileBundle {
                                         position: tile_pos,
                                         tilemap_id: TilemapId(layer_entity),
@@ -369,6 +505,7 @@ pub fn process_loaded_maps(
                                         color: TileColor(Color::WHITE),
                                         ..Default::default()
                                     });
+                                tile_entity_builder.insert((GameEntity, tile_properties));
 
                                 let tile_entity = tile_entity_builder.id();
                                 tile_storage.set(&tile_pos, tile_entity);
@@ -397,6 +534,12 @@ pub fn process_loaded_maps(
                             .insert(layer_index as u32, layer_entity);
                     }
                 }
+
+                objects_storage.entities.extend(map_paths.spawn(&mut commands));
+
+                if modified_maps.contains(map_handle) {
+                    map_reloaded_event_writer.send(MapReloadedEvent);
+                }
             }
         }
     }
@@ -0,0 +1,163 @@
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use bevy::utils::HashMap;
+use xml::attribute::OwnedAttribute;
+use xml::name::OwnedName;
+use xml::reader::{EventReader, XmlEvent};
+use xml::writer::EventWriter;
+
+/// Files of the external tilesets of a TMX map, relative to the map.
+pub fn external_tileset_sources(tmx: &[u8]) -> Result<Vec<String>> {
+    let mut sources = Vec::new();
+    for event in EventReader::new(tmx) {
+        if let XmlEvent::StartElement { name, attributes, .. } = event? {
+            if name.local_name == "tileset" {
+                sources.extend(attribute(&attributes, "source").map(str::to_string));
+            }
+        }
+    }
+    Ok(sources)
+}
+
+/**
+ * Replaces the references to external tilesets of a TMX map by the content of their TSX file, found by `source`.
+ * The tiled crate opens external tilesets from the file system itself, so they are read through the asset io and inlined.
+ */
+pub fn inline_external_tilesets(tmx: &[u8], tilesets: &HashMap<String, Vec<u8>>) -> Result<Vec<u8>> {
+    let mut reader = EventReader::new(tmx);
+    let mut writer = EventWriter::new(Vec::new());
+    loop {
+        let event = reader.next()?;
+        if let XmlEvent::EndDocument = event {
+            break;
+        }
+        if let XmlEvent::StartElement { name, attributes, .. } = &event {
+            if let (true, Some(source)) = (name.local_name == "tileset", attribute(attributes, "source")) {
+                let tsx = tilesets.get(source).ok_or_else(|| anyhow!("The tileset {source} was not read"))?;
+                let first_gid = attribute(attributes, "firstgid").ok_or_else(|| anyhow!("The tileset {source} has no firstgid"))?;
+                write_tileset(&mut writer, tsx, source, first_gid)?;
+                skip_element(&mut reader)?;
+                continue;
+            }
+        }
+        if let Some(event) = event.as_writer_event() {
+            writer.write(event)?;
+        }
+    }
+    Ok(writer.into_inner())
+}
+
+fn attribute<'a>(attributes: &'a [OwnedAttribute], name: &str) -> Option<&'a str> {
+    attributes.iter().find(|attribute| attribute.name.local_name == name).map(|attribute| attribute.value.as_str())
+}
+
+/// Writes the `<tileset>` of a TSX file with the `firstgid` given by the map.
+fn write_tileset(writer: &mut EventWriter<Vec<u8>>, tsx: &[u8], source: &str, first_gid: &str) -> Result<()> {
+    let tileset_dir = Path::new(source).parent().unwrap_or(Path::new(""));
+    let mut root = true;
+    for event in EventReader::new(tsx) {
+        let mut event = event?;
+        match &mut event {
+            XmlEvent::StartDocument { .. } | XmlEvent::EndDocument => continue,
+            XmlEvent::StartElement { name, attributes, .. } => {
+                if root {
+                    attributes.insert(0, OwnedAttribute::new(OwnedName::local("firstgid"), first_gid));
+                    root = false;
+                }
+                // images are relative to the tileset file, they are relative to the map once inlined
+                if name.local_name == "image" {
+                    for attribute in attributes.iter_mut().filter(|attribute| attribute.name.local_name == "source") {
+                        attribute.value = tileset_dir.join(&attribute.value).to_string_lossy().into_owned();
+                    }
+                }
+            }
+            _ => {}
+        }
+        if let Some(event) = event.as_writer_event() {
+            writer.write(event)?;
+        }
+    }
+    Ok(())
+}
+
+/// Skips the rest of the element whose start was just read.
+fn skip_element(reader: &mut EventReader<&[u8]>) -> Result<()> {
+    let mut depth = 1;
+    while depth > 0 {
+        match reader.next()? {
+            XmlEvent::StartElement { .. } => depth += 1,
+            XmlEvent::EndElement { .. } => depth -= 1,
+            XmlEvent::EndDocument => return Err(anyhow!("The map ends inside a tileset")),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::game::tilemap::components::TileProperties;
+
+    const TILESET: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<tileset version="1.10" name="ground" tilewidth="64" tileheight="64" tilecount="1" columns="1">
+ <image source="ground.png" width="64" height="64"/>
+ <tile id="0"><properties><property name="buildable" type="bool" value="true"/></properties></tile>
+</tileset>
+"#;
+
+    fn map(tilesets: &str) -> String {
+        format!(r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" renderorder="right-down" width="1" height="1" tilewidth="64" tileheight="64" infinite="0">
+ {tilesets}
+ <layer id="1" name="ground" width="1" height="1"><data encoding="csv">1</data></layer>
+</map>"#)
+    }
+
+    fn inline(tmx: &str, source: &str) -> tiled::Map {
+        let tilesets = HashMap::from_iter([(source.to_string(), TILESET.as_bytes().to_vec())]);
+        let tmx = inline_external_tilesets(tmx.as_bytes(), &tilesets).unwrap();
+        assert!(external_tileset_sources(&tmx).unwrap().is_empty());
+        tiled::Loader::new().load_tmx_map_from(Cursor::new(tmx), "maps/map.tmx").unwrap()
+    }
+
+    #[test]
+    fn inlined_tileset_keeps_its_tiles_and_images() {
+        let map = inline(&map(r#"<tileset firstgid="1" source="tilesets/ground.tsx"/>"#), "tilesets/ground.tsx");
+        let tileset = &map.tilesets()[0];
+        assert_eq!(tileset.name, "ground");
+        // the image stays relative to the tileset file
+        assert_eq!(tileset.image.as_ref().unwrap().source, Path::new("maps/tilesets/ground.png"));
+        assert!(TileProperties::from_tiled(&tileset.get_tile(0).unwrap().properties).buildable);
+    }
+
+    #[test]
+    fn single_quoted_attributes_are_read() {
+        let tmx = map("<tileset source='ground.tsx' firstgid='1'/>");
+        assert_eq!(external_tileset_sources(tmx.as_bytes()).unwrap(), vec!["ground.tsx".to_string()]);
+        assert_eq!(inline(&tmx, "ground.tsx").tilesets()[0].name, "ground");
+    }
+
+    #[test]
+    fn tilesets_in_comments_are_ignored() {
+        let tmx = map(r#"<!-- <tileset firstgid="1" source="old.tsx"/> --><tileset firstgid="1" source="ground.tsx"/>"#);
+        assert_eq!(external_tileset_sources(tmx.as_bytes()).unwrap(), vec!["ground.tsx".to_string()]);
+        assert_eq!(inline(&tmx, "ground.tsx").tilesets()[0].name, "ground");
+    }
+
+    #[test]
+    fn attribute_values_may_contain_brackets() {
+        let tmx = map(r#"<tileset firstgid="1" source="a>b/ground.tsx"/>"#);
+        let map = inline(&tmx, "a>b/ground.tsx");
+        assert_eq!(map.tilesets()[0].image.as_ref().unwrap().source, Path::new("maps/a>b/ground.png"));
+    }
+
+    #[test]
+    fn unread_tileset_is_an_error() {
+        let tmx = map(r#"<tileset firstgid="1" source="ground.tsx"/>"#);
+        assert!(inline_external_tilesets(tmx.as_bytes(), &HashMap::default()).is_err());
+    }
+}
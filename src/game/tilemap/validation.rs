@@ -0,0 +1,334 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use tiled::{LayerType, ObjectShape, TileLayer};
+use tiled::PropertyValue::{BoolValue, IntValue, StringValue};
+
+use crate::game::creep::path::DEFAULT_PATH_NAME;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Severity {
+    /// The map loads but probably not as intended.
+    Warning,
+    /// The map can't be played, its load fails.
+    Error,
+}
+
+#[derive(Clone, Debug)]
+pub struct MapIssue {
+    pub severity: Severity,
+    /// Object the issue is about, as `name (id)`, if any.
+    pub object: Option<String>,
+    pub message: String,
+}
+
+/// Every problem found in a map by `validate_map`.
+#[derive(Clone, Debug, Default)]
+pub struct MapReport {
+    pub issues: Vec<MapIssue>,
+}
+
+impl MapReport {
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(|issue| issue.severity == Severity::Error)
+    }
+
+    fn error(&mut self, object: Option<String>, message: impl Into<String>) {
+        self.issues.push(MapIssue {
+            severity: Severity::Error,
+            object,
+            message: message.into(),
+        });
+    }
+
+    fn warning(&mut self, object: Option<String>, message: impl Into<String>) {
+        self.issues.push(MapIssue {
+            severity: Severity::Warning,
+            object,
+            message: message.into(),
+        });
+    }
+}
+
+impl Display for MapReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for issue in self.issues.iter() {
+            let severity = match issue.severity {
+                Severity::Warning => "warning",
+                Severity::Error => "error",
+            };
+            match &issue.object {
+                Some(object) => writeln!(f, "{severity}: {object}: {}", issue.message)?,
+                None => writeln!(f, "{severity}: {}", issue.message)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/**
+ * Checks what the game expects from a map : spawners each bound to a path, finishes, paths given either by EnemyPath
+ * polylines or by waypoints numbered 0, 1, 2... without gaps, all with unique names,
+ * rectangle build zones or buildable tiles, and a finite tile layer. Everything is collected instead of stopping at the first issue,
+ * so a level designer can fix them all at once.
+ */
+pub fn validate_map(map: &tiled::Map) -> MapReport {
+    let mut report = MapReport::default();
+    // (object, name of the path) for spawners, (object, name of the finish) for paths
+    let mut spawners = Vec::<(String, Option<String>)>::new();
+    let mut finishes = Vec::<String>::new();
+    let mut build_zones = 0;
+    // waypoint objects by path name then index
+    let mut waypoints = BTreeMap::<String, BTreeMap<i32, Vec<String>>>::new();
+    let mut paths = Vec::<(String, Option<String>)>::new();
+    let mut names = ObjectNames::default();
+    let mut tile_layers = 0;
+    let mut buildable_tiles = 0;
+
+    for tileset in map.tilesets() {
+        for (tile_id, tile) in tileset.tiles() {
+            let object = format!("tile {tile_id} of tileset '{}'", tileset.name);
+            for name in ["buildable", "path", "blocked"] {
+                match tile.properties.get(name) {
+                    Some(BoolValue(true)) if name == "buildable" => buildable_tiles += 1,
+                    Some(BoolValue(_)) => {}
+                    Some(_) => report.warning(Some(object.clone()), format!("The '{name}' property must be a bool, it is ignored.")),
+                    None => {}
+                }
+            }
+            if let Some(value) = tile.properties.get("elevation") {
+                if !matches!(value, IntValue(_)) {
+                    report.warning(Some(object), "The 'elevation' property must be an int, it is ignored.");
+                }
+            }
+        }
+    }
+
+    for layer in map.layers() {
+        match layer.layer_type() {
+            LayerType::TileLayer(TileLayer::Finite(_)) => tile_layers += 1,
+            LayerType::TileLayer(TileLayer::Infinite(_)) => {
+                report.warning(None, format!("Tile layer '{}' is infinite and will be skipped, make it finite.", layer.name));
+            }
+            LayerType::ObjectLayer(object_layer) => {
+                for object_data in object_layer.object_data() {
+                    let object = format!("{} ({})", object_data.name, object_data.id());
+                    match object_data.user_type.as_str() {
+                        "Waypoint" => {
+                            let path = string_property(&mut report, &object, &object_data.properties, "path")
+                                .unwrap_or_else(|| DEFAULT_PATH_NAME.to_string());
+                            match object_data.properties.get("waypoint") {
+                                Some(IntValue(index)) => waypoints.entry(path).or_default().entry(*index).or_default().push(object),
+                                Some(_) => report.error(Some(object), "The 'waypoint' property must be an int."),
+                                None => report.error(Some(object), "Waypoint without the 'waypoint' int property giving its order."),
+                            }
+                        }
+                        "EnemyPath" => {
+                            match &object_data.shape {
+                                ObjectShape::Polyline { points } if points.len() >= 2 => {}
+                                ObjectShape::Polyline { .. } => report.error(Some(object.clone()), "Enemy path with less than 2 points."),
+                                _ => report.error(Some(object.clone()), "Enemy paths must be polylines."),
+                            }
+                            if !matches!(object_data.properties.get("curved"), None | Some(BoolValue(_))) {
+                                report.warning(Some(object.clone()), "The 'curved' property must be a bool, it is ignored.");
+                            }
+                            if !matches!(object_data.properties.get("samples"), None | Some(IntValue(1..))) {
+                                report.warning(Some(object.clone()), "The 'samples' property must be a positive int, it is ignored.");
+                            }
+                            let finish = string_property(&mut report, &object, &object_data.properties, "finish");
+                            names.paths.entry(object_data.name.clone()).or_default().push(object.clone());
+                            paths.push((object, finish));
+                        }
+                        "EnemySpawner" => {
+                            let path = string_property(&mut report, &object, &object_data.properties, "path");
+                            names.spawners.entry(object_data.name.clone()).or_default().push(object.clone());
+                            spawners.push((object, path));
+                        }
+                        "EnemyFinish" => {
+                            names.finishes.entry(object_data.name.clone()).or_default().push(object.clone());
+                            finishes.push(object);
+                        }
+                        "BuildZone" => match object_data.shape {
+                            ObjectShape::Rect { width, height } if width > 0.0 && height > 0.0 => build_zones += 1,
+                            ObjectShape::Rect { .. } => report.warning(Some(object), "Build zone with an empty area, nothing can be built in it."),
+                            _ => report.error(Some(object), "Build zones must be rectangles."),
+                        },
+                        "" => report.warning(Some(object), "Object without a class, it is ignored."),
+                        other => report.warning(Some(object), format!("Unknown class '{other}', the object is ignored.")),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if tile_layers == 0 {
+        report.error(None, "No finite tile layer, the map has no tiles to build on.");
+    }
+    if spawners.is_empty() {
+        report.error(None, "No EnemySpawner, enemies have nowhere to spawn.");
+    }
+    if finishes.is_empty() {
+        report.error(None, "No EnemyFinish, enemies have nowhere to go after the last waypoint.");
+    }
+    for path in waypoints.keys() {
+        names.paths.entry(path.clone()).or_default().push(format!("waypoints of path '{path}'"));
+    }
+    names.check_unique(&mut report);
+
+    for (object, finish) in paths.iter() {
+        if let Some(finish) = finish {
+            if !names.finishes.contains_key(finish) {
+                report.error(Some(object.clone()), format!("No EnemyFinish named '{finish}'."));
+            }
+        }
+    }
+    for (object, path) in spawners.iter() {
+        match path {
+            Some(path) if !names.paths.contains_key(path) => {
+                report.error(Some(object.clone()), format!("No EnemyPath nor waypoints named '{path}'."));
+            }
+            Some(_) => {}
+            None if names.paths.len() > 1 => {
+                report.error(Some(object.clone()), "Spawner without the 'path' property naming its path, the map has several paths.");
+            }
+            None => {}
+        }
+    }
+    for path in names.paths.keys() {
+        let used = spawners.iter().any(|(_, spawner_path)| spawner_path.as_ref().map_or(names.paths.len() == 1, |spawner_path| spawner_path == path));
+        if !used {
+            report.warning(None, format!("No EnemySpawner uses the path '{path}'."));
+        }
+    }
+
+    if build_zones == 0 && buildable_tiles == 0 {
+        report.warning(None, "No BuildZone nor buildable tile, no tower can be built.");
+    }
+
+    for (path, waypoints) in waypoints.iter() {
+        for (index, objects) in waypoints.iter() {
+            if objects.len() > 1 {
+                report.error(None, format!("Waypoint index {index} of path '{path}' is used by several waypoints: {}.", objects.join(", ")));
+            }
+        }
+        // creeps follow 0, 1, 2... and go to the finish after the last one
+        for (expected, index) in (0..).zip(waypoints.keys()) {
+            if *index != expected {
+                report.error(None, format!("Waypoint indices of path '{path}' must follow each other from 0, expected {expected} but found {index}."));
+                break;
+            }
+        }
+    }
+
+    report
+}
+
+/// Objects by name, for the kinds of objects referred to by name.
+#[derive(Default)]
+struct ObjectNames {
+    spawners: BTreeMap<String, Vec<String>>,
+    finishes: BTreeMap<String, Vec<String>>,
+    paths: BTreeMap<String, Vec<String>>,
+}
+
+impl ObjectNames {
+    fn check_unique(&self, report: &mut MapReport) {
+        for (kind, names) in [("EnemySpawner", &self.spawners), ("EnemyFinish", &self.finishes), ("path", &self.paths)] {
+            for (name, objects) in names.iter() {
+                if objects.len() > 1 {
+                    report.error(None, format!("The {kind} name '{name}' is used several times: {}.", objects.join(", ")));
+                }
+            }
+        }
+    }
+}
+
+fn string_property(report: &mut MapReport, object: &str, properties: &tiled::Properties, name: &str) -> Option<String> {
+    match properties.get(name) {
+        Some(StringValue(value)) => Some(value.clone()),
+        Some(_) => {
+            report.warning(Some(object.to_string()), format!("The '{name}' property must be a string, it is ignored."));
+            None
+        }
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// A 2x2 map with the given objects in its object layer.
+    fn map_with_objects(objects: &str) -> tiled::Map {
+        let tmx = format!(r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" renderorder="left-down" width="2" height="2" tilewidth="64" tileheight="64" infinite="0" nextlayerid="3" nextobjectid="100">
+ <tileset firstgid="1" name="default" tilewidth="64" tileheight="64" tilecount="1" columns="1">
+  <image source="tiles.png" width="64" height="64"/>
+ </tileset>
+ <layer id="1" name="tiles" width="2" height="2">
+  <data encoding="csv">1,1,1,1</data>
+ </layer>
+ <objectgroup id="2" name="objects">
+{objects}
+ </objectgroup>
+</map>"#);
+        tiled::Loader::new()
+            .load_tmx_map_from(Cursor::new(tmx.into_bytes()), "test.tmx")
+            .expect("The test map should parse.")
+    }
+
+    fn waypoint(id: u32, index: i32) -> String {
+        format!(r#"<object id="{id}" name="wp {index}" type="Waypoint" x="32" y="32"><properties><property name="waypoint" type="int" value="{index}"/></properties><point/></object>"#)
+    }
+
+    const SPAWNER: &str = r#"<object id="1" name="spawner" type="EnemySpawner" x="0" y="0"><point/></object>"#;
+    const FINISH: &str = r#"<object id="2" name="finish" type="EnemyFinish" x="96" y="96"><point/></object>"#;
+    const BUILD_ZONE: &str = r#"<object id="3" name="zone" type="BuildZone" x="0" y="64" width="64" height="64"/>"#;
+
+    fn errors(report: &MapReport) -> Vec<&str> {
+        report.issues.iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .map(|issue| issue.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn valid_map_has_no_issue() {
+        let map = map_with_objects(&[SPAWNER, FINISH, BUILD_ZONE, &waypoint(10, 0), &waypoint(11, 1)].concat());
+        let report = validate_map(&map);
+        assert!(report.issues.is_empty(), "{report}");
+    }
+
+    #[test]
+    fn duplicate_waypoint_indices_are_errors() {
+        let map = map_with_objects(&[SPAWNER, FINISH, BUILD_ZONE, &waypoint(10, 0), &waypoint(11, 0)].concat());
+        let report = validate_map(&map);
+        assert!(report.has_errors());
+        assert!(errors(&report).iter().any(|message| message.contains("used by several waypoints")), "{report}");
+    }
+
+    #[test]
+    fn gaps_in_waypoint_indices_are_errors() {
+        let map = map_with_objects(&[SPAWNER, FINISH, BUILD_ZONE, &waypoint(10, 0), &waypoint(11, 2)].concat());
+        let report = validate_map(&map);
+        assert!(errors(&report).iter().any(|message| message.contains("expected 1 but found 2")), "{report}");
+    }
+
+    #[test]
+    fn missing_finish_is_an_error() {
+        let map = map_with_objects(&[SPAWNER, BUILD_ZONE, &waypoint(10, 0)].concat());
+        let report = validate_map(&map);
+        assert!(errors(&report).iter().any(|message| message.starts_with("No EnemyFinish")), "{report}");
+    }
+
+    #[test]
+    fn build_zones_must_be_rectangles() {
+        let ellipse_zone = r#"<object id="3" name="zone" type="BuildZone" x="0" y="64" width="64" height="64"><ellipse/></object>"#;
+        let map = map_with_objects(&[SPAWNER, FINISH, ellipse_zone].concat());
+        let report = validate_map(&map);
+        assert_eq!(errors(&report), vec!["Build zones must be rectangles."]);
+    }
+}
@@ -1,13 +1,47 @@
 use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
 
+use crate::game::creep::components::DamageType;
+use crate::game::creep::effects::StatusEffect;
+use crate::game::resources::TowerType;
+
+#[derive(Component)]
+pub struct Tower {
+    pub tower_type: TowerType,
+    /// Tile entity the tower stands on, which holds the matching `BuiltTile`.
+    pub tile: Entity,
+    pub tile_pos: TilePos,
+}
+
+/// Index of the current level in the `TowerCatalog`, starting at 0.
+#[derive(Component)]
+pub struct TowerLevel(pub usize);
+
+/// What the tower achieved since it was built.
+#[derive(Component, Default)]
+pub struct TowerStats {
+    pub kills: u32,
+    pub damage_dealt: u32,
+}
+
+/// The tile under the tower is gone or out of every build zone since the map was reloaded.
+#[derive(Component)]
+pub struct OnInvalidTile;
+
+/// The tower the player clicked on, shown in the tower panel.
 #[derive(Component)]
-pub struct Tower {}
+pub struct SelectedTower;
 
 #[derive(Component)]
 pub struct ProjectileThrower {
     pub relative_start: Vec2,
     pub cooldown: Timer,
     pub range: f32,
+    pub damage: i32,
+    pub damage_type: DamageType,
+    pub projectile_sprite: &'static str,
+    /// Status effect applied to the enemy hit.
+    pub on_hit: Option<StatusEffect>,
 }
 
 #[derive(Component)]
@@ -15,11 +49,26 @@ pub struct Splasher {
     pub relative_start: Vec2,
     pub cooldown: Timer,
     pub range: f32,
+    pub damage: i32,
+    pub blast_radius: f32,
+    /// Fraction of the damage lost at the edge of the blast, from 0.0 (none) to 1.0 (all).
+    pub falloff: f32,
 }
 
 #[derive(Component)]
 pub struct Projectile {
+    /// Tower which threw the projectile.
+    pub source: Entity,
     pub damage: i32,
+    pub damage_type: DamageType,
+    pub on_hit: Option<StatusEffect>,
+}
+
+/// The projectile damages every enemy around its impact point.
+#[derive(Component)]
+pub struct Splash {
+    pub radius: f32,
+    pub falloff: f32,
 }
 
 #[derive(Component)]
@@ -35,3 +84,9 @@ pub struct Pointer {
     pub pos: Vec2,
     pub source: Vec2,
 }
+
+#[derive(Component)]
+pub struct Explosion {
+    pub timer: Timer,
+    pub radius: f32,
+}
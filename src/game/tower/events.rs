@@ -1,7 +1,36 @@
 use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::game::creep::components::DamageType;
+use crate::game::creep::effects::StatusEffect;
+use crate::game::resources::TowerType;
 
 pub struct ProjectileHitEvent {
+    /// Tower which threw the projectile.
+    pub source: Entity,
     pub damage: f32,
+    pub damage_type: DamageType,
     pub target: Entity,
+    pub on_hit: Option<StatusEffect>,
+}
+
+/// Build a tower on a tile, sent when a `PlayerCommand::BuildTower` is applied.
+pub struct BuildTowerEvent {
+    pub tower_type: TowerType,
+    pub tile_pos: TilePos,
+}
+
+pub struct UpgradeTowerEvent {
+    pub tower: Entity,
+}
+
+pub struct SellTowerEvent {
+    pub tower: Entity,
 }
 
+/// The player tried to build or upgrade a tower they can't afford.
+pub struct BuildRejectedEvent {
+    pub tower_type: TowerType,
+    pub cost: u32,
+    pub position: Vec3,
+}
@@ -7,12 +7,15 @@ use systems::*;
 
 use crate::AppState;
 use crate::game::*;
-use crate::game::tilemap::can_build;
+use crate::game::tilemap::{can_build, MapProcessingSet};
 
 mod systems;
-mod components;
-mod resources;
-mod events;
+pub mod components;
+pub mod resources;
+pub mod events;
+pub mod targeting;
+
+pub use systems::spawn_tower;
 
 pub struct TowerPlugin;
 
@@ -20,22 +23,63 @@ impl Plugin for TowerPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_event::<ProjectileHitEvent>()
+            .add_event::<BuildTowerEvent>()
+            .add_event::<BuildRejectedEvent>()
+            .add_event::<UpgradeTowerEvent>()
+            .add_event::<SellTowerEvent>()
         ;
+        app.init_resource::<TowerCatalog>();
         app.add_systems(
             (
                 throw_projectiles,
                 throw_splashes,
                 projectile_follow_step,
                 pointer_follow_step,
-                deal_projectile_damage,
             )
-                .in_set(OnUpdate(AppState::Game))
+                .chain()
+                .in_set(SimulationStage::Towers)
+                .in_schedule(CoreSchedule::FixedUpdate)
+        );
+        app.add_system(deal_projectile_damage
+            .in_set(SimulationStage::Hits)
+            .in_schedule(CoreSchedule::FixedUpdate)
+        );
+        app.add_system(animate_explosions
+            .in_set(SimulationStage::Cleanup)
+            .in_schedule(CoreSchedule::FixedUpdate)
+        );
+        app.add_systems(
+            (
+                build_tower,
+                upgrade_tower,
+                sell_tower,
+            )
+                .chain()
+                .in_set(SimulationStage::Building)
+                .in_schedule(CoreSchedule::FixedUpdate)
+        );
+        app.add_system(unlink_despawned_towers.in_set(OnUpdate(AppState::Game)));
+        app.add_system(relink_towers_after_map_reload
+            .after(MapProcessingSet)
+            .in_set(OnUpdate(AppState::Game))
         );
         app
             .add_system(build_tower_at_click
                 .run_if(can_build)
                 .in_set(OnUpdate(AppState::Game))
             )
+            .add_system(select_tower_at_click
+                .run_if(can_select)
+                .in_set(OnUpdate(AppState::Game))
+            )
+            .add_system(unselect_towers.in_schedule(OnEnter(GameState::Building)))
         ;
     }
 }
+
+pub fn can_select(
+    game_state: Res<State<GameState>>,
+    ui_state: Res<State<UiState>>,
+) -> bool {
+    game_state.0 == GameState::Playing && ui_state.0 == UiState::Nothing
+}
@@ -1 +1,66 @@
 use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::game::resources::TowerType;
+
+/// Stats of a tower at a given level.
+pub struct TowerLevelStats {
+    /// Gold needed to build (first level) or to upgrade to this level.
+    pub cost: u32,
+    pub damage: i32,
+    pub range: f32,
+    /// Seconds between two shots.
+    pub cooldown: f32,
+    pub sprite: &'static str,
+}
+
+/// Every level of every tower type.
+#[derive(Resource)]
+pub struct TowerCatalog {
+    pub levels: HashMap<TowerType, Vec<TowerLevelStats>>,
+    /// Part of the gold spent on a tower given back when it is sold.
+    pub refund_ratio: f32,
+}
+
+impl TowerCatalog {
+    pub fn level(&self, tower_type: TowerType, level: usize) -> Option<&TowerLevelStats> {
+        self.levels.get(&tower_type).and_then(|levels| levels.get(level))
+    }
+
+    /// Gold needed to build the first level of this tower type.
+    pub fn build_cost(&self, tower_type: TowerType) -> u32 {
+        self.level(tower_type, 0).map_or(0, |stats| stats.cost)
+    }
+
+    /// Gold given back when selling a tower of this type at this level.
+    pub fn sell_value(&self, tower_type: TowerType, level: usize) -> u32 {
+        let invested: u32 = self.levels.get(&tower_type)
+            .map_or(0, |levels| levels.iter().take(level + 1).map(|stats| stats.cost).sum());
+        (invested as f32 * self.refund_ratio) as u32
+    }
+}
+
+impl Default for TowerCatalog {
+    fn default() -> Self {
+        let mut levels = HashMap::default();
+        levels.insert(TowerType::Arrow, vec![
+            TowerLevelStats { cost: 50, damage: 40, range: 450.0, cooldown: 1.0, sprite: "sprites/tower.png" },
+            TowerLevelStats { cost: 60, damage: 60, range: 500.0, cooldown: 0.9, sprite: "sprites/tower.png" },
+            TowerLevelStats { cost: 100, damage: 90, range: 550.0, cooldown: 0.75, sprite: "sprites/tower.png" },
+        ]);
+        levels.insert(TowerType::Bomb, vec![
+            TowerLevelStats { cost: 80, damage: 40, range: 300.0, cooldown: 3.0, sprite: "sprites/tower_bomb.png" },
+            TowerLevelStats { cost: 90, damage: 60, range: 330.0, cooldown: 2.7, sprite: "sprites/tower_bomb.png" },
+            TowerLevelStats { cost: 140, damage: 90, range: 360.0, cooldown: 2.4, sprite: "sprites/tower_bomb.png" },
+        ]);
+        levels.insert(TowerType::Frost, vec![
+            TowerLevelStats { cost: 70, damage: 10, range: 400.0, cooldown: 1.2, sprite: "sprites/tower_frost.png" },
+            TowerLevelStats { cost: 70, damage: 15, range: 430.0, cooldown: 1.0, sprite: "sprites/tower_frost.png" },
+            TowerLevelStats { cost: 110, damage: 20, range: 460.0, cooldown: 0.8, sprite: "sprites/tower_frost.png" },
+        ]);
+        Self {
+            levels,
+            refund_ratio: 0.7,
+        }
+    }
+}
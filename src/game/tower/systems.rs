@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use bevy::input::ButtonState;
+use bevy::log;
 use bevy::input::mouse::MouseButtonInput;
 use bevy::math::Vec3Swizzles;
 use bevy::prelude::*;
@@ -6,147 +9,425 @@ use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
 use bevy::sprite::Anchor;
 use bevy_ecs_tilemap::prelude::*;
 
-use crate::game::creep::components::{Dying, Enemy, Health};
+use crate::game::components::{GameEntity, Interpolated};
+use crate::game::creep::components::{DamageType, Dying, Enemy, Health, Resistances};
+use crate::game::creep::effects::{StatusEffect, StatusEffectKind, StatusEffects};
 use crate::game::creep::events::KilledEvent;
-use crate::game::resources::{BuildTower, TowerType};
-use crate::game::tilemap::components::{BuiltTile, SelectedForBuild};
+use crate::game::player_commands::commands::{PlayerCommand, tile_of};
+use crate::game::player_commands::resources::PendingCommands;
+use crate::game::resources::{BuildTower, Gold, SimulationClock, TowerType};
+use crate::game::tilemap::components::{BuildZone, BuiltTile, SelectedForBuild, TileProperties};
+use crate::game::tilemap::events::MapReloadedEvent;
+use crate::game::tilemap::is_buildable_tile;
+use crate::game::tilemap::resources::CursorPos;
 
 use super::components::*;
-use super::events::ProjectileHitEvent;
+use super::events::{BuildRejectedEvent, BuildTowerEvent, ProjectileHitEvent, SellTowerEvent, UpgradeTowerEvent};
+use super::resources::{TowerCatalog, TowerLevelStats};
+use super::targeting::{Targets, TargetingMode};
+
+/// Tint of the towers standing on a tile made invalid by a map reload.
+const INVALID_TOWER_COLOR: Color = Color::rgb(1.0, 0.4, 0.4);
 
 /**
- * On click, spawn a new tower at the selected_for_build tile.
+ * On click, asks to build the chosen tower at the selected_for_build tile.
  */
 pub fn build_tower_at_click(
-    mut commands: Commands,
     mut clicked_event_reader: EventReader<MouseButtonInput>,
-    selected_for_build_tile_q: Query<(Entity, &TilePos), With<SelectedForBuild>>,
-    tilemap_q: Query<(&TilemapGridSize, &TilemapType, &GlobalTransform)>,
-    asset_server: Res<AssetServer>,
-    build_tower: Res<BuildTower>
+    selected_for_build_tile_q: Query<&TilePos, With<SelectedForBuild>>,
+    build_tower: Res<BuildTower>,
+    mut pending_commands: ResMut<PendingCommands>,
 ) {
     for click in clicked_event_reader.iter() {
         if click.button != MouseButton::Left || click.state != ButtonState::Released {
             continue;
         }
-        let Ok((tile_entity, tile_pos)) = selected_for_build_tile_q.get_single() else {
+        let Ok(tile_pos) = selected_for_build_tile_q.get_single() else {
             return;
         };
-        let Ok((tilemap_grid_size, tilemap_type, tilemap_transform)) = tilemap_q.get_single() else {
-            return;
+        pending_commands.push(PlayerCommand::BuildTower {
+            tower_type: build_tower.tower_type,
+            tile: tile_of(tile_pos),
+        });
+    }
+}
+
+/**
+ * Spawns the requested towers, if the tile is free, can be built on and the player can afford it.
+ */
+pub fn build_tower(
+    mut commands: Commands,
+    mut build_tower_event_reader: EventReader<BuildTowerEvent>,
+    tilemap_q: Query<(&TileStorage, &TilemapGridSize, &TilemapType, &TilemapTileSize, &GlobalTransform)>,
+    built_tiles_q: Query<&BuiltTile>,
+    build_zones_q: Query<&BuildZone>,
+    tile_properties_q: Query<&TileProperties>,
+    asset_server: Res<AssetServer>,
+    tower_catalog: Res<TowerCatalog>,
+    mut gold: ResMut<Gold>,
+    mut build_rejected_event_writer: EventWriter<BuildRejectedEvent>,
+) {
+    let Ok((tile_storage, tilemap_grid_size, tilemap_type, tile_size, tilemap_transform)) = tilemap_q.get_single() else {
+        return;
+    };
+    // towers built during this run of the system are not in built_tiles_q yet
+    let mut built_this_time = Vec::<Entity>::new();
+
+    for event in build_tower_event_reader.iter() {
+        let tile_pos = &event.tile_pos;
+        let Some(tile_entity) = tile_storage.get(tile_pos) else {
+            log::debug!("build_tower: no tile at {:?}", tile_pos);
+            continue;
         };
+        if built_tiles_q.get(tile_entity).is_ok() || built_this_time.contains(&tile_entity) {
+            log::debug!("build_tower: tile {:?} already has a tower", tile_pos);
+            continue;
+        }
 
         let tile_world_pos = tile_pos.center_in_world(&tilemap_grid_size, &tilemap_type);
 
-        commands.entity(tile_entity).insert(BuiltTile {});
+        if !is_buildable_tile(tile_pos, tile_properties_q.get(tile_entity).ok(), tilemap_grid_size, tilemap_type, tile_size, &build_zones_q) {
+            log::debug!("build_tower: tile {:?} can't be built on", tile_pos);
+            continue;
+        }
 
-        let tower_id = commands.spawn((
-            Tower {},
-            Name::new("Tower")
-        )).id();
+        let Some(stats) = tower_catalog.level(event.tower_type, 0) else {
+            continue;
+        };
+        if gold.current < stats.cost {
+            log::debug!("build_tower: not enough gold for {:?}", event.tower_type);
+            build_rejected_event_writer.send(BuildRejectedEvent {
+                tower_type: event.tower_type,
+                cost: stats.cost,
+                position: Vec3::from((tile_world_pos, 0.0)) + tilemap_transform.translation(),
+            });
+            continue;
+        }
+        gold.current -= stats.cost;
 
-        println!("build_tower_at_click: {:?}", build_tower.tower_type);
+        let translation = Vec3::from((tile_world_pos, 10.0)) + tilemap_transform.translation();
+        spawn_tower(&mut commands, &asset_server, stats, event.tower_type, 0, tile_entity, *tile_pos, translation);
+        built_this_time.push(tile_entity);
 
-        let mut texture: Handle<Image>;
-        match build_tower.tower_type {
-            TowerType::Arrow => {
-                texture = asset_server.load("sprites/tower.png");
-                commands.entity(tower_id).insert(
-                    ProjectileThrower {
-                        relative_start: Vec2::new(0.0, 0.25 * 64.0),
-                        cooldown: Timer::from_seconds(1.0, TimerMode::Repeating),
-                        range: 450.0,
-                    },
-                );
-            }
-            TowerType::Bomb => {
-                texture = asset_server.load("sprites/tower_bomb.png");
-                commands.entity(tower_id).insert(
-                    Splasher {
-                        relative_start: Vec2::new(0.0, 0.25 * 64.0),
-                        cooldown: Timer::from_seconds(3.0, TimerMode::Repeating),
-                        range: 300.0,
-                    },
-                );
-            }
+        log::info!("build_tower: {:?} at {:?}", event.tower_type, tile_pos);
+    }
+}
+
+/**
+ * Links towers to the tiles of a reloaded map, and flags those whose tile is gone or can't be built on anymore.
+ * Flagged towers keep working, the player decides whether to sell them.
+ */
+pub fn relink_towers_after_map_reload(
+    mut commands: Commands,
+    mut map_reloaded_event_reader: EventReader<MapReloadedEvent>,
+    tilemap_q: Query<(&TileStorage, &TilemapGridSize, &TilemapType, &TilemapTileSize)>,
+    build_zones_q: Query<&BuildZone>,
+    tile_properties_q: Query<&TileProperties>,
+    mut tower_query: Query<(Entity, &mut Tower, &mut Sprite)>,
+) {
+    if map_reloaded_event_reader.iter().count() == 0 {
+        return;
+    }
+    let Ok((tile_storage, tilemap_grid_size, tilemap_type, tile_size)) = tilemap_q.get_single() else {
+        return;
+    };
+
+    for (tower_entity, mut tower, mut sprite) in tower_query.iter_mut() {
+        let tile_entity = tile_storage.get(&tower.tile_pos);
+        if let Some(tile_entity) = tile_entity {
+            commands.entity(tile_entity).insert(BuiltTile {
+                tower: tower_entity,
+            });
+            tower.tile = tile_entity;
         }
+        let valid = tile_entity.is_some_and(|tile_entity| is_buildable_tile(
+            &tower.tile_pos, tile_properties_q.get(tile_entity).ok(), tilemap_grid_size, tilemap_type, tile_size, &build_zones_q,
+        ));
+        if valid {
+            commands.entity(tower_entity).remove::<OnInvalidTile>();
+            sprite.color = Color::WHITE;
+        } else {
+            log::warn!("Tower at {:?} is not on a valid tile anymore after the map reload.", tower.tile_pos);
+            commands.entity(tower_entity).insert(OnInvalidTile);
+            sprite.color = INVALID_TOWER_COLOR;
+        }
+    }
+}
 
-        commands.entity(tower_id).insert(
-            SpriteBundle {
-                transform: Transform::from_translation(Vec3::from((tile_world_pos, 10.0)) + tilemap_transform.translation()),
-                texture,
-                sprite: Sprite {
-                    custom_size: Some(Vec2::new(64.0, 64.0)),
-                    anchor: Anchor::Center,
-                    ..default()
+/**
+ * Spawns a tower of the given level on a tile and links the tile to it, without any check nor cost.
+ */
+pub fn spawn_tower(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    stats: &TowerLevelStats,
+    tower_type: TowerType,
+    level: usize,
+    tile_entity: Entity,
+    tile_pos: TilePos,
+    translation: Vec3,
+) -> Entity {
+    let tower_id = commands.spawn((
+        Tower {
+            tower_type,
+            tile: tile_entity,
+            tile_pos,
+        },
+        TowerLevel(level),
+        TowerStats::default(),
+        TargetingMode::default(),
+        GameEntity,
+        Name::new("Tower")
+    )).id();
+
+    commands.entity(tile_entity).insert(BuiltTile {
+        tower: tower_id,
+    });
+
+    match tower_type {
+        TowerType::Arrow => {
+            commands.entity(tower_id).insert(
+                ProjectileThrower {
+                    relative_start: Vec2::new(0.0, 0.25 * 64.0),
+                    cooldown: Timer::from_seconds(stats.cooldown, TimerMode::Repeating),
+                    range: stats.range,
+                    damage: stats.damage,
+                    damage_type: DamageType::Physical,
+                    projectile_sprite: "sprites/arrow.png",
+                    on_hit: None,
                 },
+            );
+        }
+        TowerType::Frost => {
+            commands.entity(tower_id).insert(
+                ProjectileThrower {
+                    relative_start: Vec2::new(0.0, 0.25 * 64.0),
+                    cooldown: Timer::from_seconds(stats.cooldown, TimerMode::Repeating),
+                    range: stats.range,
+                    damage: stats.damage,
+                    damage_type: DamageType::Frost,
+                    projectile_sprite: "sprites/arrow_frost.png",
+                    on_hit: Some(StatusEffect::new(StatusEffectKind::Slow { factor: 0.5 }, 2.0)),
+                },
+            );
+        }
+        TowerType::Bomb => {
+            commands.entity(tower_id).insert(
+                Splasher {
+                    relative_start: Vec2::new(0.0, 0.25 * 64.0),
+                    cooldown: Timer::from_seconds(stats.cooldown, TimerMode::Repeating),
+                    range: stats.range,
+                    damage: stats.damage,
+                    blast_radius: 96.0,
+                    falloff: 0.5,
+                },
+            );
+        }
+    }
+
+    commands.entity(tower_id).insert(
+        SpriteBundle {
+            transform: Transform::from_translation(translation),
+            texture: asset_server.load(stats.sprite),
+            sprite: Sprite {
+                custom_size: Some(Vec2::new(64.0, 64.0)),
+                anchor: Anchor::Center,
                 ..default()
             },
-        );
+            ..default()
+        },
+    );
+
+    tower_id
+}
+
+/**
+ * Moves a tower to its next level in place : the cooldown progress and the targeting mode are kept.
+ */
+pub fn upgrade_tower(
+    mut upgrade_tower_event_reader: EventReader<UpgradeTowerEvent>,
+    mut tower_query: Query<(
+        &Tower,
+        &mut TowerLevel,
+        &Transform,
+        &mut Handle<Image>,
+        Option<&mut ProjectileThrower>,
+        Option<&mut Splasher>,
+    )>,
+    asset_server: Res<AssetServer>,
+    tower_catalog: Res<TowerCatalog>,
+    mut gold: ResMut<Gold>,
+    mut build_rejected_event_writer: EventWriter<BuildRejectedEvent>,
+) {
+    for event in upgrade_tower_event_reader.iter() {
+        let Ok((tower, mut tower_level, tower_transform, mut texture, projectile_thrower, splasher)) = tower_query.get_mut(event.tower) else {
+            continue;
+        };
+        let Some(stats) = tower_catalog.level(tower.tower_type, tower_level.0 + 1) else {
+            // already at max level
+            continue;
+        };
+        if gold.current < stats.cost {
+            log::debug!("upgrade_tower: not enough gold for {:?}", tower.tower_type);
+            build_rejected_event_writer.send(BuildRejectedEvent {
+                tower_type: tower.tower_type,
+                cost: stats.cost,
+                position: tower_transform.translation,
+            });
+            continue;
+        }
+        gold.current -= stats.cost;
+        tower_level.0 += 1;
+
+        if let Some(mut projectile_thrower) = projectile_thrower {
+            projectile_thrower.cooldown.set_duration(Duration::from_secs_f32(stats.cooldown));
+            projectile_thrower.range = stats.range;
+            projectile_thrower.damage = stats.damage;
+        }
+        if let Some(mut splasher) = splasher {
+            splasher.cooldown.set_duration(Duration::from_secs_f32(stats.cooldown));
+            splasher.range = stats.range;
+            splasher.damage = stats.damage;
+        }
+        *texture = asset_server.load(stats.sprite);
+
+        log::info!("upgrade_tower: {:?} to level {}", tower.tower_type, tower_level.0 + 1);
     }
 }
 
-pub fn throw_projectiles(
+/**
+ * Refunds part of the tower cost, then frees its tile so something else can be built there.
+ */
+pub fn sell_tower(
     mut commands: Commands,
-    mut thrower_query: Query<(&mut ProjectileThrower, &Transform)>,
-    asset_server: Res<AssetServer>,
-    enemies_query: Query<(Entity, &Transform), With<Enemy>>,
-    time: Res<Time>,
+    mut sell_tower_event_reader: EventReader<SellTowerEvent>,
+    tower_query: Query<(&Tower, &TowerLevel)>,
+    tower_catalog: Res<TowerCatalog>,
+    mut gold: ResMut<Gold>,
 ) {
-    for (mut projectile_thrower, thrower_transform) in thrower_query.iter_mut() {
-        projectile_thrower.cooldown.tick(time.delta());
-        if !projectile_thrower.cooldown.finished() {
+    for event in sell_tower_event_reader.iter() {
+        let Ok((tower, tower_level)) = tower_query.get(event.tower) else {
             continue;
+        };
+
+        if let Some(mut tile_entity) = commands.get_entity(tower.tile) {
+            tile_entity.remove::<BuiltTile>();
         }
-        let mut closest_enemy: Option<(Entity, &Transform)> = None;
-        for (enemy_entity, enemy_transform) in enemies_query.iter() {
-            let distance_to_enemy = (enemy_transform.translation - thrower_transform.translation).length_squared();
 
-            if distance_to_enemy > projectile_thrower.range * projectile_thrower.range {
-                continue;
-            }
+        let refund = tower_catalog.sell_value(tower.tower_type, tower_level.0);
+        gold.current += refund;
+        log::info!("sell_tower: {:?} for {} gold", tower.tower_type, refund);
+        commands.entity(event.tower).despawn_recursive();
+    }
+}
+
+/**
+ * On click, select the tower under the cursor, or unselect everything when clicking elsewhere.
+ */
+pub fn select_tower_at_click(
+    mut commands: Commands,
+    mut clicked_event_reader: EventReader<MouseButtonInput>,
+    cursor_pos: Res<CursorPos>,
+    tilemap_q: Query<(&TilemapSize, &TilemapGridSize, &TilemapType, &TileStorage, &GlobalTransform)>,
+    built_tile_q: Query<&BuiltTile>,
+    selected_tower_query: Query<Entity, With<SelectedTower>>,
+) {
+    for click in clicked_event_reader.iter() {
+        if click.button != MouseButton::Left || click.state != ButtonState::Released {
+            continue;
+        }
+
+        let clicked_tower = tilemap_q.iter().find_map(|(map_size, grid_size, map_type, tile_storage, map_transform)| {
+            let cursor_in_map_pos = cursor_pos.0 - map_transform.translation().xy();
+            let tile_pos = TilePos::from_world_pos(&cursor_in_map_pos, map_size, grid_size, map_type)?;
+            let tile_entity = tile_storage.get(&tile_pos)?;
+            built_tile_q.get(tile_entity).ok().map(|built_tile| built_tile.tower)
+        });
 
-            if closest_enemy.is_none() {
-                closest_enemy = Some((enemy_entity, enemy_transform));
-                continue;
+        for selected_tower_entity in selected_tower_query.iter() {
+            if Some(selected_tower_entity) != clicked_tower {
+                commands.entity(selected_tower_entity).remove::<SelectedTower>();
             }
+        }
+        if let Some(tower_entity) = clicked_tower {
+            commands.entity(tower_entity).insert(SelectedTower);
+        }
+    }
+}
 
-            let distance_to_closest_enemy = (closest_enemy.unwrap().1.translation - thrower_transform.translation).length_squared();
-            if distance_to_enemy < distance_to_closest_enemy
-            {
-                closest_enemy = Some((enemy_entity, enemy_transform));
+/**
+ * Frees the tiles of towers despawned without going through `sell_tower`.
+ */
+pub fn unlink_despawned_towers(
+    mut commands: Commands,
+    mut removed_towers: RemovedComponents<Tower>,
+    built_tile_q: Query<(Entity, &BuiltTile)>,
+) {
+    for removed_tower_entity in removed_towers.iter() {
+        for (tile_entity, built_tile) in built_tile_q.iter() {
+            if built_tile.tower == removed_tower_entity {
+                commands.entity(tile_entity).remove::<BuiltTile>();
             }
         }
+    }
+}
 
-        if closest_enemy.is_none() {
+pub fn unselect_towers(
+    mut commands: Commands,
+    selected_tower_query: Query<Entity, With<SelectedTower>>,
+) {
+    for selected_tower_entity in selected_tower_query.iter() {
+        commands.entity(selected_tower_entity).remove::<SelectedTower>();
+    }
+}
+
+pub fn throw_projectiles(
+    mut commands: Commands,
+    mut thrower_query: Query<(Entity, &mut ProjectileThrower, &Transform, &TargetingMode)>,
+    asset_server: Res<AssetServer>,
+    targets: Targets,
+    simulation_clock: Res<SimulationClock>,
+) {
+    for (thrower_entity, mut projectile_thrower, thrower_transform, targeting_mode) in thrower_query.iter_mut() {
+        projectile_thrower.cooldown.tick(simulation_clock.delta());
+        if !projectile_thrower.cooldown.finished() {
             continue;
         }
+        let Some(target) = targets.select(*targeting_mode, thrower_transform.translation, projectile_thrower.range) else {
+            continue;
+        };
 
-        let direction = (closest_enemy.unwrap().1.translation - thrower_transform.translation).xy().normalize();
+        let direction = (target.translation - thrower_transform.translation).xy().normalize();
+        let start = thrower_transform.translation + Vec3::from((projectile_thrower.relative_start, 0.0));
 
         commands.spawn(
             (
                 Projectile {
-                    damage: 40,
+                    source: thrower_entity,
+                    damage: projectile_thrower.damage,
+                    damage_type: projectile_thrower.damage_type,
+                    on_hit: projectile_thrower.on_hit.clone(),
                 },
                 Follower {
                     speed: 800.0,
-                    target: closest_enemy.unwrap().0,
+                    target: target.entity,
                 },
                 SpriteBundle {
-                    transform: Transform::from_translation(thrower_transform.translation + Vec3::from((projectile_thrower.relative_start, 0.0))).with_rotation(
+                    transform: Transform::from_translation(start).with_rotation(
                         Quat::from_rotation_arc_2d(
                             Vec2::new(1.0, 0.0),
                             direction,
                         )
                     ).with_scale(Vec3::splat(0.25)),
-                    texture: asset_server.load("sprites/arrow.png"),
+                    texture: asset_server.load(projectile_thrower.projectile_sprite),
                     sprite: Sprite {
                         anchor: Anchor::CenterRight,
                         ..default()
                     },
                     ..Default::default()
                 },
+                Interpolated::new(start),
+                GameEntity,
                 Name::new("Projectile"),
             ),
         );
@@ -155,53 +436,41 @@ pub fn throw_projectiles(
 
 pub fn throw_splashes(
     mut commands: Commands,
-    mut splasher_query: Query<(&mut Splasher, &Transform)>,
+    mut splasher_query: Query<(Entity, &mut Splasher, &Transform, &TargetingMode)>,
     asset_server: Res<AssetServer>,
-    enemies_query: Query<(Entity, &Transform), With<Enemy>>,
-    time: Res<Time>,
+    targets: Targets,
+    simulation_clock: Res<SimulationClock>,
 ) {
-    for (mut splasher, thrower_transform) in splasher_query.iter_mut() {
-        splasher.cooldown.tick(time.delta());
+    for (thrower_entity, mut splasher, thrower_transform, targeting_mode) in splasher_query.iter_mut() {
+        splasher.cooldown.tick(simulation_clock.delta());
         if !splasher.cooldown.finished() {
             continue;
         }
-        let mut closest_enemy: Option<(Entity, &Transform)> = None;
-        for (enemy_entity, enemy_transform) in enemies_query.iter() {
-            let distance_to_enemy = (enemy_transform.translation - thrower_transform.translation).xy().length_squared();
-
-            if distance_to_enemy > splasher.range * splasher.range {
-                continue;
-            }
-
-            if closest_enemy.is_none() {
-                closest_enemy = Some((enemy_entity, enemy_transform));
-                continue;
-            }
-
-            let distance_to_closest_enemy = (closest_enemy.unwrap().1.translation - thrower_transform.translation).xy().length_squared();
-            if distance_to_enemy < distance_to_closest_enemy
-            {
-                closest_enemy = Some((enemy_entity, enemy_transform));
-            }
-        }
-
-        if closest_enemy.is_none() {
+        let Some(target) = targets.select(*targeting_mode, thrower_transform.translation, splasher.range) else {
             continue;
-        }
+        };
 
+        let start = Vec3::from((thrower_transform.translation.xy() + splasher.relative_start, 10.0));
         commands.spawn(
             (
                 Projectile {
-                    damage: 40,
+                    source: thrower_entity,
+                    damage: splasher.damage,
+                    damage_type: DamageType::Explosive,
+                    on_hit: None,
+                },
+                Splash {
+                    radius: splasher.blast_radius,
+                    falloff: splasher.falloff,
                 },
                 Pointer {
                     speed: 100.0,
-                    target: closest_enemy.unwrap().1.translation.xy(),
+                    target: target.translation.xy(),
                     pos: thrower_transform.translation.xy() + splasher.relative_start,
                     source: thrower_transform.translation.xy() + splasher.relative_start,
                 },
                 SpriteBundle {
-                    transform: Transform::from_translation(Vec3::from((thrower_transform.translation.xy() + splasher.relative_start, 10.0))).with_scale(Vec3::splat(0.25)),
+                    transform: Transform::from_translation(start).with_scale(Vec3::splat(0.25)),
                     texture: asset_server.load("sprites/bomb.png"),
                     sprite: Sprite {
                         anchor: Anchor::CenterRight,
@@ -209,6 +478,8 @@ pub fn throw_splashes(
                     },
                     ..Default::default()
                 },
+                Interpolated::new(start),
+                GameEntity,
                 Name::new("Projectile"),
             ),
         );
@@ -220,12 +491,15 @@ pub fn projectile_follow_step(
     mut projectile_query: Query<(Entity, &Follower, &mut Transform, &Projectile)>,
     mut projectile_hit_event_writer: EventWriter<ProjectileHitEvent>,
     target_query: Query<&Transform, (Without<Projectile>, Without<Dying>)>,
-    time: Res<Time>,
+    simulation_clock: Res<SimulationClock>,
 ) {
     for (follower_entity, follower, mut follower_transform, projectile) in projectile_query.iter_mut() {
         if let Ok(target_transform) = target_query.get(follower.target) {
-            let direction_to_target = (target_transform.translation - follower_transform.translation).xy().normalize();
-            follower_transform.translation += Vec3::from((direction_to_target, 0.0)) * follower.speed * time.delta_seconds();
+            let to_target = (target_transform.translation - follower_transform.translation).xy();
+            let direction_to_target = to_target.normalize();
+            // never move past the target, so a long step at high game speed can't tunnel through it
+            let step = (follower.speed * simulation_clock.delta_seconds()).min(to_target.length());
+            follower_transform.translation += Vec3::from((direction_to_target, 0.0)) * step;
             follower_transform.rotation = Quat::from_rotation_arc_2d(
                 Vec2::new(1.0, 0.0),
                 direction_to_target,
@@ -234,8 +508,11 @@ pub fn projectile_follow_step(
             // check if projectile is close enough to target
             if (target_transform.translation - follower_transform.translation).xy().length_squared() < 20.0 * 20.0 {
                 projectile_hit_event_writer.send(ProjectileHitEvent {
+                    source: projectile.source,
                     damage: projectile.damage as f32,
+                    damage_type: projectile.damage_type,
                     target: follower.target,
+                    on_hit: projectile.on_hit.clone(),
                 });
                 // println!("despawn projectile because hit target {:?}", follower_entity);
                 commands.entity(follower_entity).despawn_recursive();
@@ -251,31 +528,60 @@ pub fn projectile_follow_step(
 
 pub fn pointer_follow_step(
     mut commands: Commands,
-    mut pointer_query: Query<(Entity, &mut Pointer, &mut Transform, &Projectile)>,
-    time: Res<Time>,
+    mut pointer_query: Query<(Entity, &mut Pointer, &mut Transform, &Projectile, &Splash)>,
+    mut projectile_hit_event_writer: EventWriter<ProjectileHitEvent>,
+    enemies_query: Query<(Entity, &Transform), (With<Enemy>, Without<Dying>, Without<Pointer>)>,
+    asset_server: Res<AssetServer>,
+    simulation_clock: Res<SimulationClock>,
 ) {
-    for (follower_entity, mut pointer, mut follower_transform, projectile) in pointer_query.iter_mut() {
+    for (follower_entity, mut pointer, mut follower_transform, projectile, splash) in pointer_query.iter_mut() {
         let direction_to_target = (pointer.target - pointer.pos).normalize();
 
         let speed = pointer.speed;
-        pointer.pos += direction_to_target * speed * time.delta_seconds();
+        // never move past the target, so a long step at high game speed can't overshoot it
+        let step = (speed * simulation_clock.delta_seconds()).min((pointer.target - pointer.pos).length());
+        pointer.pos += direction_to_target * step;
 
         follower_transform.translation.x = pointer.pos.x;
 
         let percent_to_target = 1.0 - (pointer.target - pointer.pos).length() / (pointer.target - pointer.source).length();
-        println!("percent_to_target: {}", percent_to_target);
-        // println!("delta y: {}", -100.0 * (percent_to_target - 0.5) * pointer.speed * time.delta_seconds());
-        // follower_transform.translation.y += -300.0 * (percent_to_target - 0.5) * time.delta_seconds();
         let coef_parabolic = 2.0 * (0.5 - (percent_to_target - 0.5).abs());
-        println!("coef_parabolic: {}", coef_parabolic);
         follower_transform.translation.y = pointer.pos.y + 30.0 * coef_parabolic * coef_parabolic;
 
         // check if projectile is close enough to target
         if (pointer.target - pointer.pos).length_squared() < 10.0 * 10.0 {
-            // projectile_hit_event_writer.send(ProjectileHitEvent {
-            //     damage: projectile.damage as f32,
-            //     target: follower.target,
-            // });
+            for (enemy_entity, enemy_transform) in enemies_query.iter() {
+                let distance = (enemy_transform.translation.xy() - pointer.target).length();
+                if distance > splash.radius {
+                    continue;
+                }
+                let falloff = splash.falloff * distance / splash.radius;
+                projectile_hit_event_writer.send(ProjectileHitEvent {
+                    source: projectile.source,
+                    damage: projectile.damage as f32 * (1.0 - falloff),
+                    damage_type: projectile.damage_type,
+                    target: enemy_entity,
+                    on_hit: projectile.on_hit.clone(),
+                });
+            }
+            commands.spawn((
+                Explosion {
+                    timer: Timer::from_seconds(0.4, TimerMode::Once),
+                    radius: splash.radius,
+                },
+                SpriteBundle {
+                    transform: Transform::from_translation(Vec3::from((pointer.target, 20.0))),
+                    texture: asset_server.load("sprites/explosion.png"),
+                    sprite: Sprite {
+                        custom_size: Some(Vec2::ZERO),
+                        anchor: Anchor::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                GameEntity,
+                Name::new("Explosion"),
+            ));
             // println!("despawn projectile because hit target {:?}", follower_entity);
             commands.entity(follower_entity).despawn_recursive();
         }
@@ -284,16 +590,35 @@ pub fn pointer_follow_step(
 
 pub fn deal_projectile_damage(
     mut projectile_hit_event_reader: EventReader<ProjectileHitEvent>,
-    mut health_query: Query<&mut Health>,
+    mut health_query: Query<(&mut Health, Option<&Resistances>, Option<&mut StatusEffects>)>,
+    mut tower_stats_query: Query<&mut TowerStats>,
     mut event_writer: EventWriter<KilledEvent>,
 ) {
     for event in projectile_hit_event_reader.iter() {
-        let Ok(mut target_health) = health_query.get_mut(event.target) else {
+        let Ok((mut target_health, resistances, status_effects)) = health_query.get_mut(event.target) else {
             // does not exist anymore
             continue;
         };
-        target_health.current -= (event.damage) as i32;
-        if target_health.current <= 0 {
+        let mut multiplier = resistances.map_or(1.0, |resistances| resistances.multiplier(event.damage_type));
+        if let Some(mut status_effects) = status_effects {
+            multiplier *= status_effects.damage_taken_multiplier();
+            if let Some(on_hit) = &event.on_hit {
+                status_effects.apply(on_hit.clone());
+            }
+        }
+        let was_alive = target_health.current > 0;
+        let damage = (event.damage * multiplier) as i32;
+        target_health.current -= damage;
+        // the source may have been sold since it threw the projectile
+        let mut source_stats = tower_stats_query.get_mut(event.source).ok();
+        if let Some(source_stats) = source_stats.as_mut() {
+            source_stats.damage_dealt += damage.max(0) as u32;
+        }
+        // only the hit which brings the enemy down counts as its kill
+        if was_alive && target_health.current <= 0 {
+            if let Some(source_stats) = source_stats.as_mut() {
+                source_stats.kills += 1;
+            }
             event_writer.send(KilledEvent {
                 who: event.target,
             });
@@ -301,3 +626,22 @@ pub fn deal_projectile_damage(
     }
 }
 
+/**
+ * Explosions grow up to their blast radius then fade out.
+ */
+pub fn animate_explosions(
+    mut commands: Commands,
+    mut explosion_query: Query<(Entity, &mut Explosion, &mut Sprite)>,
+    simulation_clock: Res<SimulationClock>,
+) {
+    for (explosion_entity, mut explosion, mut sprite) in explosion_query.iter_mut() {
+        explosion.timer.tick(simulation_clock.delta());
+        if explosion.timer.finished() {
+            commands.entity(explosion_entity).despawn_recursive();
+            continue;
+        }
+        let percent = explosion.timer.percent();
+        sprite.custom_size = Some(Vec2::splat(explosion.radius * 2.0 * percent.sqrt()));
+        sprite.color.set_a(1.0 - percent * percent);
+    }
+}
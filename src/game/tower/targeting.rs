@@ -0,0 +1,119 @@
+use std::cmp::Ordering;
+
+use bevy::ecs::system::SystemParam;
+use bevy::math::Vec3Swizzles;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::creep::components::{Dying, Enemy, EnemyFinish, Health, WaypointFollower};
+use crate::game::creep::path::Path;
+
+/// Which enemy in range a tower shoots at.
+#[derive(Component, Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TargetingMode {
+    /// Furthest along the path.
+    #[default]
+    First,
+    /// Least advanced along the path.
+    Last,
+    /// Most current health.
+    Strongest,
+    /// Least current health.
+    Weakest,
+    Closest,
+}
+
+impl TargetingMode {
+    pub fn next(&self) -> Self {
+        match self {
+            TargetingMode::First => TargetingMode::Last,
+            TargetingMode::Last => TargetingMode::Strongest,
+            TargetingMode::Strongest => TargetingMode::Weakest,
+            TargetingMode::Weakest => TargetingMode::Closest,
+            TargetingMode::Closest => TargetingMode::First,
+        }
+    }
+}
+
+/// An enemy a tower could shoot at.
+#[derive(Copy, Clone)]
+pub struct Target {
+    pub entity: Entity,
+    pub translation: Vec3,
+    health: i32,
+    /// Length left to walk to the finish, along the path.
+    distance_to_finish: f32,
+    distance_to_tower: f32,
+}
+
+impl Target {
+    /// Ordering of two targets along the path : Greater means `self` is ahead of `other`.
+    fn cmp_progress(&self, other: &Target) -> Ordering {
+        other.distance_to_finish.total_cmp(&self.distance_to_finish)
+    }
+
+    /// Ordering of two targets for a mode : Greater means `self` should be preferred.
+    fn cmp_for(&self, other: &Target, mode: TargetingMode) -> Ordering {
+        match mode {
+            TargetingMode::First => self.cmp_progress(other),
+            TargetingMode::Last => other.cmp_progress(self),
+            TargetingMode::Strongest => self.health.cmp(&other.health),
+            TargetingMode::Weakest => other.health.cmp(&self.health),
+            TargetingMode::Closest => other.distance_to_tower.total_cmp(&self.distance_to_tower),
+        }
+    }
+}
+
+/**
+ * Every living enemy towers can aim at, with what is needed to rank them.
+ */
+#[derive(SystemParam)]
+pub struct Targets<'w, 's> {
+    enemies_query: Query<'w, 's, (Entity, &'static Transform, &'static Health, Option<&'static WaypointFollower>), (With<Enemy>, Without<Dying>)>,
+    path_query: Query<'w, 's, &'static Path>,
+    finish_query: Query<'w, 's, &'static EnemyFinish>,
+    tile_map_query: Query<'w, 's, (&'static GlobalTransform, &'static TilemapTileSize), With<TileStorage>>,
+}
+
+impl<'w, 's> Targets<'w, 's> {
+    /// Best enemy within `range` of `origin` according to `mode`.
+    pub fn select(&self, mode: TargetingMode, origin: Vec3, range: f32) -> Option<Target> {
+        let tilemap_top_left = self.tile_map_query.get_single()
+            .map(|(tilemap_transform, tile_size)| tilemap_transform.translation().xy() - Vec2::new(tile_size.x / 2.0, tile_size.y / 2.0))
+            .unwrap_or(Vec2::ZERO);
+
+        let mut best_target: Option<Target> = None;
+        for (enemy_entity, enemy_transform, health, follower) in self.enemies_query.iter() {
+            let distance_to_tower = (enemy_transform.translation - origin).xy().length();
+            if distance_to_tower > range {
+                continue;
+            }
+
+            let waypoint_index = follower.map_or(0, |follower| follower.index);
+            let path = follower.and_then(|follower| Path::find(self.path_query.iter(), &follower.path));
+            let next_position = match path {
+                Some(path) => path.waypoint(waypoint_index).or(path.finish),
+                None => self.finish_query.iter().next().map(|finish| finish.position),
+            };
+            let distance_to_next = next_position
+                .map_or(0.0, |position| (position + tilemap_top_left - enemy_transform.translation.xy()).length());
+            // paths do not share waypoints, enemies on different paths are compared by what they have left to walk
+            let distance_to_finish = distance_to_next + path.map_or(0.0, |path| path.remaining_length(waypoint_index));
+
+            let target = Target {
+                entity: enemy_entity,
+                translation: enemy_transform.translation,
+                health: health.current,
+                distance_to_finish,
+                distance_to_tower,
+            };
+            match best_target {
+                Some(best) if target.cmp_for(&best, mode) != Ordering::Greater => {}
+                _ => best_target = Some(target),
+            }
+        }
+
+        best_target
+    }
+}
@@ -2,7 +2,31 @@ use bevy::prelude::*;
 
 use crate::game::resources::TowerType;
 
+#[derive(Component)]
+pub struct ActionBar;
+
 #[derive(Component)]
 pub struct BuildTowerAction {
     pub tower_type: TowerType,
 }
+
+/// World-space text drifting up then disappearing, used for short feedbacks.
+#[derive(Component)]
+pub struct FloatingText {
+    pub timer: Timer,
+}
+
+#[derive(Component)]
+pub struct TowerPanel;
+
+#[derive(Component)]
+pub struct CycleTargetingButton;
+
+#[derive(Component)]
+pub struct TargetingModeText;
+
+#[derive(Component)]
+pub struct UpgradeTowerButton;
+
+#[derive(Component)]
+pub struct SellTowerButton;
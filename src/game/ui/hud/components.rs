@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct Hud;
+
+#[derive(Component)]
+pub struct GoldText;
+
+#[derive(Component)]
+pub struct LivesText;
+
+#[derive(Component)]
+pub struct WaveText;
+
+#[derive(Component)]
+pub struct NextWaveText;
+
+#[derive(Component)]
+pub struct GameSpeedButton {
+    pub speed: f32,
+}
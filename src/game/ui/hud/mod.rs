@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use systems::*;
+use crate::AppState;
+use crate::game::creep::resources::WaveState;
+use crate::game::resources::{Gold, PlayerLives};
+
+mod systems;
+mod components;
+mod styles;
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(spawn_hud.in_schedule(OnEnter(AppState::Game)));
+        app.add_systems(
+            (
+                update_gold_text.run_if(resource_changed::<Gold>()),
+                update_lives_text.run_if(resource_changed::<PlayerLives>()),
+                update_wave_texts.run_if(resource_exists::<WaveState>().and_then(resource_changed::<WaveState>())),
+                interact_with_game_speed_buttons,
+                update_game_speed_buttons.after(interact_with_game_speed_buttons),
+            ).in_set(OnUpdate(AppState::Game))
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+use crate::styles::{get_text_style, HOVERED_BUTTON_COLOR, NORMAL_BUTTON_COLOR, PRESSED_BUTTON_COLOR};
+
+pub const HUD_STYLE: Style = Style {
+    position_type: PositionType::Absolute,
+    position: UiRect {
+        left: Val::Px(16.0),
+        top: Val::Px(16.0),
+        right: Val::Auto,
+        bottom: Val::Auto,
+    },
+    flex_direction: FlexDirection::Column,
+    padding: UiRect::all(Val::Px(12.0)),
+    gap: Size::new(Val::Px(4.0), Val::Px(4.0)),
+    ..Style::DEFAULT
+};
+
+pub const GAME_SPEED_BAR_STYLE: Style = Style {
+    flex_direction: FlexDirection::Row,
+    gap: Size::new(Val::Px(4.0), Val::Px(4.0)),
+    ..Style::DEFAULT
+};
+
+pub const GAME_SPEED_BUTTON_STYLE: Style = Style {
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Px(48.0), Val::Px(32.0)),
+    ..Style::DEFAULT
+};
+
+pub const HUD_BACKGROUND_COLOR: Color = Color::rgba(0.1, 0.1, 0.1, 0.85);
+
+pub const GOLD_COLOR: Color = Color::rgb(1.0, 0.85, 0.2);
+pub const LIVES_COLOR: Color = Color::rgb(1.0, 0.4, 0.4);
+
+pub const GAME_SPEED_BUTTON_COLOR: Color = NORMAL_BUTTON_COLOR;
+pub const HOVERED_GAME_SPEED_BUTTON_COLOR: Color = HOVERED_BUTTON_COLOR;
+pub const ACTIVE_GAME_SPEED_BUTTON_COLOR: Color = PRESSED_BUTTON_COLOR;
+
+pub fn get_hud_text_style(asset_server: &Res<AssetServer>, color: Color) -> TextStyle {
+    get_text_style(asset_server, 28.0, color)
+}
@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+
+use crate::game::components::GameEntity;
+use crate::game::creep::resources::{WavePhase, WaveState};
+use crate::game::creep::waves::WaveSchedule;
+use crate::game::UiState;
+use crate::game::player_commands::commands::PlayerCommand;
+use crate::game::player_commands::resources::PendingCommands;
+use crate::game::resources::{GAME_SPEEDS, Gold, PlayerLives, SimulationClock};
+
+use super::components::*;
+use super::styles::*;
+
+pub fn spawn_hud(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    gold: Res<Gold>,
+    player_lives: Res<PlayerLives>,
+    simulation_clock: Res<SimulationClock>,
+) {
+    build_hud(&mut commands, &asset_server, &gold, &player_lives, &simulation_clock);
+}
+
+pub fn build_hud(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    gold: &Gold,
+    player_lives: &PlayerLives,
+    simulation_clock: &SimulationClock,
+) -> Entity {
+    commands
+        .spawn((
+            NodeBundle {
+                style: HUD_STYLE,
+                background_color: HUD_BACKGROUND_COLOR.into(),
+                ..default()
+            },
+            Hud,
+            GameEntity,
+            Name::new("Hud"),
+        ))
+        .with_children(|parent: &mut ChildBuilder| {
+            parent.spawn((
+                TextBundle {
+                    text: Text::from_section(gold_label(gold), get_hud_text_style(&asset_server, GOLD_COLOR)),
+                    ..default()
+                },
+                GoldText,
+            ));
+            parent.spawn((
+                TextBundle {
+                    text: Text::from_section(lives_label(player_lives), get_hud_text_style(&asset_server, LIVES_COLOR)),
+                    ..default()
+                },
+                LivesText,
+            ));
+            parent.spawn((
+                TextBundle {
+                    text: Text::from_section("Wave - / -", get_hud_text_style(&asset_server, Color::WHITE)),
+                    ..default()
+                },
+                WaveText,
+            ));
+            parent.spawn((
+                TextBundle {
+                    text: Text::from_section("", get_hud_text_style(&asset_server, Color::WHITE)),
+                    ..default()
+                },
+                NextWaveText,
+            ));
+            parent
+                .spawn(NodeBundle {
+                    style: GAME_SPEED_BAR_STYLE,
+                    ..default()
+                })
+                .with_children(|parent: &mut ChildBuilder| {
+                    for speed in GAME_SPEEDS {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: GAME_SPEED_BUTTON_STYLE,
+                                    background_color: game_speed_button_color(speed, simulation_clock).into(),
+                                    ..default()
+                                },
+                                GameSpeedButton {
+                                    speed,
+                                },
+                            ))
+                            .with_children(|parent: &mut ChildBuilder| {
+                                parent.spawn(TextBundle {
+                                    text: Text::from_section(format!("x{}", speed), get_hud_text_style(&asset_server, Color::WHITE)),
+                                    ..default()
+                                });
+                            });
+                    }
+                });
+        })
+        .id()
+}
+
+fn game_speed_button_color(speed: f32, simulation_clock: &SimulationClock) -> Color {
+    if speed == simulation_clock.speed {
+        ACTIVE_GAME_SPEED_BUTTON_COLOR
+    } else {
+        GAME_SPEED_BUTTON_COLOR
+    }
+}
+
+fn gold_label(gold: &Gold) -> String {
+    format!("Gold: {}", gold.current)
+}
+
+fn lives_label(player_lives: &PlayerLives) -> String {
+    format!("Lives: {}", player_lives.current)
+}
+
+/// Only touches the text when the value differs, so the layout is not recomputed every frame.
+fn set_text(text: &mut Mut<Text>, value: String) {
+    if text.sections[0].value != value {
+        text.sections[0].value = value;
+    }
+}
+
+pub fn update_gold_text(
+    gold: Res<Gold>,
+    mut text_query: Query<&mut Text, With<GoldText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        set_text(&mut text, gold_label(&gold));
+    }
+}
+
+pub fn update_lives_text(
+    player_lives: Res<PlayerLives>,
+    mut text_query: Query<&mut Text, With<LivesText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        set_text(&mut text, lives_label(&player_lives));
+    }
+}
+
+pub fn update_wave_texts(
+    wave_state: Res<WaveState>,
+    schedules: Res<Assets<WaveSchedule>>,
+    mut wave_text_query: Query<&mut Text, (With<WaveText>, Without<NextWaveText>)>,
+    mut next_wave_text_query: Query<&mut Text, (With<NextWaveText>, Without<WaveText>)>,
+) {
+    let Some(schedule) = schedules.get(&wave_state.schedule) else {
+        return;
+    };
+
+    for mut text in wave_text_query.iter_mut() {
+        set_text(&mut text, format!("Wave {} / {}", wave_state.wave_index + 1, schedule.waves.len()));
+    }
+
+    let next_wave_label = match wave_state.phase {
+        WavePhase::Countdown => format!("Next wave in {}s", wave_state.timer.remaining_secs().ceil()),
+        WavePhase::Finished => "All waves cleared".to_string(),
+        _ => String::new(),
+    };
+    for mut text in next_wave_text_query.iter_mut() {
+        set_text(&mut text, next_wave_label.clone());
+    }
+}
+
+pub fn interact_with_game_speed_buttons(
+    button_query: Query<(&Interaction, &GameSpeedButton), Changed<Interaction>>,
+    mut pending_commands: ResMut<PendingCommands>,
+    mut ui_next_state: ResMut<NextState<UiState>>,
+) {
+    for (interaction, game_speed_button) in button_query.iter() {
+        match *interaction {
+            Interaction::Clicked => {
+                pending_commands.push(PlayerCommand::SetSpeed {
+                    speed: game_speed_button.speed,
+                });
+                ui_next_state.set(UiState::ChoosingAction);
+            }
+            Interaction::Hovered => {
+                ui_next_state.set(UiState::ChoosingAction);
+            }
+            Interaction::None => {
+                ui_next_state.set(UiState::Nothing);
+            }
+        }
+    }
+}
+
+/**
+ * Highlights the button of the current game speed, whether it was picked with a click or a hotkey.
+ */
+pub fn update_game_speed_buttons(
+    simulation_clock: Res<SimulationClock>,
+    mut button_query: Query<(&Interaction, &GameSpeedButton, &mut BackgroundColor)>,
+) {
+    for (interaction, game_speed_button, mut background_color) in button_query.iter_mut() {
+        let color = match *interaction {
+            Interaction::Hovered if game_speed_button.speed != simulation_clock.speed => HOVERED_GAME_SPEED_BUTTON_COLOR,
+            _ => game_speed_button_color(game_speed_button.speed, &simulation_clock),
+        };
+        if background_color.0 != color {
+            background_color.0 = color;
+        }
+    }
+}
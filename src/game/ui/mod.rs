@@ -1,16 +1,34 @@
 use bevy::prelude::*;
 
+use hud::HudPlugin;
+use pause_menu::PauseMenuPlugin;
 use systems::*;
 use crate::AppState;
 
 mod systems;
 mod components;
+mod styles;
+mod hud;
+mod pause_menu;
 
 pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
+        app.add_plugin(HudPlugin);
+        app.add_plugin(PauseMenuPlugin);
         app.add_system(spawn_action_bar.in_schedule(OnEnter(AppState::Game)));
-        app.add_system(interact_with_build_action.in_set(OnUpdate(AppState::Game)));
+        app.add_systems(
+            (
+                interact_with_build_action,
+                show_build_rejected,
+                animate_floating_texts,
+                refresh_tower_panel,
+                update_targeting_mode_text,
+                interact_with_cycle_targeting_button,
+                interact_with_upgrade_tower_button,
+                interact_with_sell_tower_button,
+            ).in_set(OnUpdate(AppState::Game))
+        );
     }
 }
@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct PauseMenu;
+
+#[derive(Component)]
+pub struct ResumeButton;
+
+#[derive(Component)]
+pub struct SaveButton;
+
+#[derive(Component)]
+pub struct RestartButton;
+
+#[derive(Component)]
+pub struct QuitToMenuButton;
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use systems::*;
+use crate::game::GameState;
+
+mod systems;
+mod components;
+mod styles;
+
+pub struct PauseMenuPlugin;
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_system(spawn_pause_menu.in_schedule(OnEnter(GameState::Paused)))
+            .add_systems(
+                (
+                    interact_with_resume_button,
+                    interact_with_save_button,
+                    interact_with_restart_button,
+                    interact_with_quit_to_menu_button,
+                ).in_set(OnUpdate(GameState::Paused))
+            )
+            .add_system(despawn_pause_menu.in_schedule(OnExit(GameState::Paused)))
+        ;
+    }
+}
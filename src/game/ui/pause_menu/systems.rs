@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::AppState;
+use crate::game::player_commands::commands::PlayerCommand;
+use crate::game::player_commands::resources::PendingCommands;
+use crate::game::save::events::SaveGameEvent;
+use crate::game::ui::styles::{get_panel_button_text_style, PANEL_BUTTON_STYLE};
+use crate::styles::{get_title_text_style, HOVERED_BUTTON_COLOR, NORMAL_BUTTON_COLOR, PRESSED_BUTTON_COLOR};
+
+use super::components::*;
+use super::styles::*;
+
+pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    build_pause_menu(&mut commands, &asset_server);
+}
+
+pub fn despawn_pause_menu(mut commands: Commands, pause_menu_query: Query<Entity, With<PauseMenu>>) {
+    if let Ok(pause_menu_entity) = pause_menu_query.get_single() {
+        commands.entity(pause_menu_entity).despawn_recursive();
+    }
+}
+
+pub fn interact_with_resume_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<ResumeButton>),
+    >,
+    mut pending_commands: ResMut<PendingCommands>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                pending_commands.push(PlayerCommand::Resume);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn interact_with_save_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<SaveButton>),
+    >,
+    mut save_game_event_writer: EventWriter<SaveGameEvent>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                save_game_event_writer.send(SaveGameEvent);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+/**
+ * Re-entering `AppState::Game` tears the current game down and starts a new one.
+ */
+pub fn interact_with_restart_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<RestartButton>),
+    >,
+    mut app_next_state: ResMut<NextState<AppState>>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                app_next_state.set(AppState::Game);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn interact_with_quit_to_menu_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<QuitToMenuButton>),
+    >,
+    mut app_next_state: ResMut<NextState<AppState>>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                app_next_state.set(AppState::MainMenu);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn build_pause_menu(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+    commands
+        .spawn((
+            NodeBundle {
+                style: PAUSE_MENU_STYLE,
+                background_color: OVERLAY_COLOR.into(),
+                // the overlay swallows clicks so the game below can't be interacted with
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            PauseMenu,
+            Name::new("PauseMenu"),
+        ))
+        .with_children(|parent: &mut ChildBuilder| {
+            parent.spawn(TextBundle {
+                text: Text::from_section("Paused", get_title_text_style(&asset_server, Color::WHITE))
+                    .with_alignment(TextAlignment::Center),
+                ..default()
+            });
+            spawn_pause_menu_button(parent, asset_server, "Resume", ResumeButton);
+            spawn_pause_menu_button(parent, asset_server, "Save", SaveButton);
+            spawn_pause_menu_button(parent, asset_server, "Restart", RestartButton);
+            spawn_pause_menu_button(parent, asset_server, "Quit to menu", QuitToMenuButton);
+        })
+        .id()
+}
+
+fn spawn_pause_menu_button(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>, label: &str, marker: impl Component) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: PANEL_BUTTON_STYLE,
+                background_color: NORMAL_BUTTON_COLOR.into(),
+                ..default()
+            },
+            marker,
+        ))
+        .with_children(|parent: &mut ChildBuilder| {
+            parent.spawn(TextBundle {
+                text: Text::from_section(label, get_panel_button_text_style(&asset_server))
+                    .with_alignment(TextAlignment::Center),
+                ..default()
+            });
+        });
+}
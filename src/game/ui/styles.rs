@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+
+use crate::styles::get_text_style;
+
+pub const PANEL_BACKGROUND_COLOR: Color = Color::rgba(0.1, 0.1, 0.1, 0.85);
+
+pub const TOWER_PANEL_STYLE: Style = Style {
+    position_type: PositionType::Absolute,
+    position: UiRect {
+        right: Val::Px(16.0),
+        top: Val::Px(16.0),
+        left: Val::Auto,
+        bottom: Val::Auto,
+    },
+    flex_direction: FlexDirection::Column,
+    align_items: AlignItems::Stretch,
+    padding: UiRect::all(Val::Px(12.0)),
+    gap: Size::new(Val::Px(8.0), Val::Px(8.0)),
+    ..Style::DEFAULT
+};
+
+pub const PANEL_BUTTON_STYLE: Style = Style {
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Px(220.0), Val::Px(40.0)),
+    ..Style::DEFAULT
+};
+
+pub fn get_panel_title_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    get_text_style(asset_server, 24.0, Color::WHITE)
+}
+
+pub fn get_panel_button_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    get_text_style(asset_server, 20.0, Color::WHITE)
+}
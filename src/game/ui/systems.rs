@@ -2,14 +2,23 @@ use std::any::Any;
 use bevy::prelude::*;
 
 use crate::game::{GameState, UiState};
+use crate::game::components::GameEntity;
 use crate::game::resources::{BuildTower, TowerType};
-use crate::game::ui::components::BuildTowerAction;
+use crate::game::tower::components::{SelectedTower, Tower, TowerLevel};
+use crate::game::player_commands::commands::{PlayerCommand, tile_of};
+use crate::game::player_commands::resources::PendingCommands;
+use crate::game::tower::events::BuildRejectedEvent;
+use crate::game::tower::resources::TowerCatalog;
+use crate::game::tower::targeting::TargetingMode;
+use crate::game::ui::components::{ActionBar, BuildTowerAction, CycleTargetingButton, FloatingText, TargetingModeText, SellTowerButton, TowerPanel, UpgradeTowerButton};
+use crate::game::ui::styles::*;
+use crate::styles::*;
 
-pub fn spawn_action_bar(mut commands: Commands, asset_server: Res<AssetServer>) {
-    build_action_bar(&mut commands, &asset_server);
+pub fn spawn_action_bar(mut commands: Commands, asset_server: Res<AssetServer>, tower_catalog: Res<TowerCatalog>) {
+    build_action_bar(&mut commands, &asset_server, &tower_catalog);
 }
 
-pub fn build_action_bar(commands: &mut Commands, asset_server: &Res<AssetServer>) {
+pub fn build_action_bar(commands: &mut Commands, asset_server: &Res<AssetServer>, tower_catalog: &TowerCatalog) {
     commands
         .spawn(
             (
@@ -25,6 +34,8 @@ pub fn build_action_bar(commands: &mut Commands, asset_server: &Res<AssetServer>
                     },
                     ..default()
                 },
+                ActionBar,
+                GameEntity,
             )
         )
         .with_children(|parent: &mut ChildBuilder| {
@@ -32,6 +43,8 @@ pub fn build_action_bar(commands: &mut Commands, asset_server: &Res<AssetServer>
                 ButtonBundle {
                     style: Style {
                         size: Size::new(Val::Px(96.0), Val::Px(96.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::End,
                         ..Style::DEFAULT
                     },
                     image: asset_server.load("ui/build_tower.png").into(),
@@ -40,11 +53,15 @@ pub fn build_action_bar(commands: &mut Commands, asset_server: &Res<AssetServer>
                 BuildTowerAction {
                     tower_type: TowerType::Arrow,
                 },
-            ));
+            )).with_children(|parent: &mut ChildBuilder| {
+                spawn_cost_label(parent, asset_server, tower_catalog.build_cost(TowerType::Arrow));
+            });
             parent.spawn((
                 ButtonBundle {
                     style: Style {
                         size: Size::new(Val::Px(96.0), Val::Px(96.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::End,
                         ..Style::DEFAULT
                     },
                     image: asset_server.load("ui/build_tower_bomb.png").into(),
@@ -53,10 +70,39 @@ pub fn build_action_bar(commands: &mut Commands, asset_server: &Res<AssetServer>
                 BuildTowerAction {
                     tower_type: TowerType::Bomb,
                 },
-            ));
+            )).with_children(|parent: &mut ChildBuilder| {
+                spawn_cost_label(parent, asset_server, tower_catalog.build_cost(TowerType::Bomb));
+            });
+            parent.spawn((
+                ButtonBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(96.0), Val::Px(96.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::End,
+                        ..Style::DEFAULT
+                    },
+                    image: asset_server.load("ui/build_tower_frost.png").into(),
+                    ..default()
+                },
+                BuildTowerAction {
+                    tower_type: TowerType::Frost,
+                },
+            )).with_children(|parent: &mut ChildBuilder| {
+                spawn_cost_label(parent, asset_server, tower_catalog.build_cost(TowerType::Frost));
+            });
         });
 }
 
+fn spawn_cost_label(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>, cost: u32) {
+    parent.spawn(TextBundle {
+        text: Text::from_section(
+            format!("{} g", cost),
+            get_text_style(asset_server, 20.0, Color::rgb(1.0, 0.85, 0.2)),
+        ),
+        ..default()
+    });
+}
+
 pub fn interact_with_build_action(
     mut button_query: Query<
         (&Interaction, &mut BackgroundColor, &BuildTowerAction),
@@ -98,3 +144,274 @@ pub fn interact_with_build_action(
         }
     }
 }
+
+pub fn show_build_rejected(
+    mut commands: Commands,
+    mut build_rejected_event_reader: EventReader<BuildRejectedEvent>,
+    asset_server: Res<AssetServer>,
+) {
+    for event in build_rejected_event_reader.iter() {
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    format!("Not enough gold for {:?} ({} g)", event.tower_type, event.cost),
+                    get_text_style(&asset_server, 24.0, Color::rgb(1.0, 0.3, 0.3)),
+                ).with_alignment(TextAlignment::Center),
+                transform: Transform::from_translation(event.position + Vec3::new(0.0, 32.0, 100.0)),
+                ..default()
+            },
+            FloatingText {
+                timer: Timer::from_seconds(1.5, TimerMode::Once),
+            },
+            GameEntity,
+            Name::new("FloatingText"),
+        ));
+    }
+}
+
+pub fn animate_floating_texts(
+    mut commands: Commands,
+    mut floating_text_query: Query<(Entity, &mut FloatingText, &mut Transform, &mut Text)>,
+    time: Res<Time>,
+) {
+    for (entity, mut floating_text, mut transform, mut text) in floating_text_query.iter_mut() {
+        floating_text.timer.tick(time.delta());
+        if floating_text.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        transform.translation.y += 30.0 * time.delta_seconds();
+        let alpha = 1.0 - floating_text.timer.percent();
+        for section in text.sections.iter_mut() {
+            section.style.color.set_a(alpha);
+        }
+    }
+}
+
+/**
+ * Rebuilds the tower panel when the selected tower changes or is upgraded.
+ */
+pub fn refresh_tower_panel(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    tower_catalog: Res<TowerCatalog>,
+    selected_tower_query: Query<(&Tower, &TowerLevel, &TargetingMode), (With<SelectedTower>, Or<(Added<SelectedTower>, Changed<TowerLevel>)>)>,
+    mut unselected_towers: RemovedComponents<SelectedTower>,
+    tower_panel_query: Query<Entity, With<TowerPanel>>,
+    mut ui_next_state: ResMut<NextState<UiState>>,
+) {
+    let has_unselected = unselected_towers.iter().count() > 0;
+    if !has_unselected && selected_tower_query.is_empty() {
+        return;
+    }
+
+    for tower_panel_entity in tower_panel_query.iter() {
+        commands.entity(tower_panel_entity).despawn_recursive();
+    }
+    // the hovered button may have been despawned with the panel
+    ui_next_state.set(UiState::Nothing);
+
+    if let Some((tower, tower_level, targeting_mode)) = selected_tower_query.iter().next() {
+        build_tower_panel(&mut commands, &asset_server, &tower_catalog, tower, tower_level, *targeting_mode);
+    }
+}
+
+pub fn build_tower_panel(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    tower_catalog: &TowerCatalog,
+    tower: &Tower,
+    tower_level: &TowerLevel,
+    targeting_mode: TargetingMode,
+) -> Entity {
+    let upgrade_label = match tower_catalog.level(tower.tower_type, tower_level.0 + 1) {
+        Some(next_level) => format!("Upgrade ({} g)", next_level.cost),
+        None => "Max level".to_string(),
+    };
+    let sell_label = format!("Sell (+{} g)", tower_catalog.sell_value(tower.tower_type, tower_level.0));
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: TOWER_PANEL_STYLE,
+                background_color: PANEL_BACKGROUND_COLOR.into(),
+                ..default()
+            },
+            TowerPanel,
+            GameEntity,
+            Name::new("TowerPanel"),
+        ))
+        .with_children(|parent: &mut ChildBuilder| {
+            parent.spawn(TextBundle {
+                text: Text::from_section(
+                    format!("{:?} tower - level {}", tower.tower_type, tower_level.0 + 1),
+                    get_panel_title_text_style(&asset_server),
+                ),
+                ..default()
+            });
+            // Targeting mode button
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: PANEL_BUTTON_STYLE,
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    CycleTargetingButton,
+                ))
+                .with_children(|parent: &mut ChildBuilder| {
+                    parent.spawn((
+                        TextBundle {
+                            text: Text::from_section(
+                                format!("Target: {:?}", targeting_mode),
+                                get_panel_button_text_style(&asset_server),
+                            ),
+                            ..default()
+                        },
+                        TargetingModeText,
+                    ));
+                });
+            // Upgrade button
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: PANEL_BUTTON_STYLE,
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    UpgradeTowerButton,
+                ))
+                .with_children(|parent: &mut ChildBuilder| {
+                    parent.spawn(TextBundle {
+                        text: Text::from_section(upgrade_label, get_panel_button_text_style(&asset_server)),
+                        ..default()
+                    });
+                });
+            // Sell button
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: PANEL_BUTTON_STYLE,
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    SellTowerButton,
+                ))
+                .with_children(|parent: &mut ChildBuilder| {
+                    parent.spawn(TextBundle {
+                        text: Text::from_section(sell_label, get_panel_button_text_style(&asset_server)),
+                        ..default()
+                    });
+                });
+        })
+        .id()
+}
+
+pub fn update_targeting_mode_text(
+    selected_tower_query: Query<&TargetingMode, (With<SelectedTower>, Changed<TargetingMode>)>,
+    mut text_query: Query<&mut Text, With<TargetingModeText>>,
+) {
+    let Ok(targeting_mode) = selected_tower_query.get_single() else {
+        return;
+    };
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format!("Target: {:?}", targeting_mode);
+    }
+}
+
+pub fn interact_with_cycle_targeting_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<CycleTargetingButton>),
+    >,
+    selected_tower_query: Query<(&Tower, &TargetingMode), With<SelectedTower>>,
+    mut pending_commands: ResMut<PendingCommands>,
+    mut ui_next_state: ResMut<NextState<UiState>>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                for (tower, targeting_mode) in selected_tower_query.iter() {
+                    pending_commands.push(PlayerCommand::SetTargeting {
+                        tile: tile_of(&tower.tile_pos),
+                        mode: targeting_mode.next(),
+                    });
+                }
+                ui_next_state.set(UiState::ChoosingAction);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+                ui_next_state.set(UiState::ChoosingAction);
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+                ui_next_state.set(UiState::Nothing);
+            }
+        }
+    }
+}
+
+pub fn interact_with_upgrade_tower_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<UpgradeTowerButton>),
+    >,
+    selected_tower_query: Query<&Tower, With<SelectedTower>>,
+    mut pending_commands: ResMut<PendingCommands>,
+    mut ui_next_state: ResMut<NextState<UiState>>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                for tower in selected_tower_query.iter() {
+                    pending_commands.push(PlayerCommand::UpgradeTower {
+                        tile: tile_of(&tower.tile_pos),
+                    });
+                }
+                ui_next_state.set(UiState::ChoosingAction);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+                ui_next_state.set(UiState::ChoosingAction);
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+                ui_next_state.set(UiState::Nothing);
+            }
+        }
+    }
+}
+
+pub fn interact_with_sell_tower_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<SellTowerButton>),
+    >,
+    selected_tower_query: Query<&Tower, With<SelectedTower>>,
+    mut pending_commands: ResMut<PendingCommands>,
+    mut ui_next_state: ResMut<NextState<UiState>>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                for tower in selected_tower_query.iter() {
+                    pending_commands.push(PlayerCommand::SellTower {
+                        tile: tile_of(&tower.tile_pos),
+                    });
+                }
+                ui_next_state.set(UiState::ChoosingAction);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+                ui_next_state.set(UiState::ChoosingAction);
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+                ui_next_state.set(UiState::Nothing);
+            }
+        }
+    }
+}
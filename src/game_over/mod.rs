@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+
+use systems::*;
+
+mod components;
+mod systems;
+mod styles;
+
+pub struct GameOverPlugin;
+
+impl Plugin for GameOverPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_system(spawn_game_over_menu.in_schedule(OnEnter(AppState::GameOver)))
+            .add_systems(
+                (
+                    interact_with_retry_button,
+                    interact_with_main_menu_button,
+                ).in_set(OnUpdate(AppState::GameOver))
+            )
+            .add_system(despawn_game_over_menu.in_schedule(OnExit(AppState::GameOver)))
+        ;
+    }
+}
@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+use crate::styles::get_text_style;
+
+pub const VICTORY_COLOR: Color = Color::rgb(0.4, 0.9, 0.4);
+pub const DEFEAT_COLOR: Color = Color::rgb(0.9, 0.3, 0.3);
+
+pub const GAME_OVER_MENU_STYLE: Style = Style {
+    flex_direction: FlexDirection::Column,
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+    gap: Size::new(Val::Px(8.0), Val::Px(8.0)),
+    ..Style::DEFAULT
+};
+
+pub const BUTTON_STYLE: Style = Style {
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Px(240.0), Val::Px(80.0)),
+    ..Style::DEFAULT
+};
+
+pub const STATS_STYLE: Style = Style {
+    flex_direction: FlexDirection::Column,
+    align_items: AlignItems::Center,
+    margin: UiRect::all(Val::Px(16.0)),
+    ..Style::DEFAULT
+};
+
+pub fn get_stats_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    get_text_style(asset_server, 28.0, Color::WHITE)
+}
@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::game::resources::GameStats;
+
+use super::components::*;
+use super::styles::*;
+use crate::styles::*;
+
+pub fn spawn_game_over_menu(mut commands: Commands, asset_server: Res<AssetServer>, game_stats: Res<GameStats>) {
+    build_game_over_menu(&mut commands, &asset_server, &game_stats);
+}
+
+pub fn despawn_game_over_menu(mut commands: Commands, game_over_menu_query: Query<Entity, With<GameOverMenu>>) {
+    if let Ok(game_over_menu_entity) = game_over_menu_query.get_single() {
+        commands.entity(game_over_menu_entity).despawn_recursive();
+    }
+}
+
+pub fn interact_with_retry_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<RetryButton>),
+    >,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                next_app_state.set(AppState::Game);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn interact_with_main_menu_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<MainMenuButton>),
+    >,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                next_app_state.set(AppState::MainMenu);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn build_game_over_menu(commands: &mut Commands, asset_server: &Res<AssetServer>, game_stats: &GameStats) -> Entity {
+    let (title, title_color) = if game_stats.victory {
+        ("Victory!", VICTORY_COLOR)
+    } else {
+        ("Defeat", DEFEAT_COLOR)
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: GAME_OVER_MENU_STYLE,
+                ..default()
+            },
+            GameOverMenu,
+        ))
+        .with_children(|parent: &mut ChildBuilder| {
+            // Title
+            parent.spawn(TextBundle {
+                text: Text::from_section(title, get_title_text_style(&asset_server, title_color))
+                    .with_alignment(TextAlignment::Center),
+                ..default()
+            });
+            // Stats
+            parent
+                .spawn(NodeBundle {
+                    style: STATS_STYLE,
+                    ..default()
+                })
+                .with_children(|parent: &mut ChildBuilder| {
+                    for line in [
+                        format!("Waves survived: {}", game_stats.waves_survived),
+                        format!("Enemies killed: {}", game_stats.enemies_killed),
+                        format!("Gold earned: {}", game_stats.gold_earned),
+                    ] {
+                        parent.spawn(TextBundle {
+                            text: Text::from_section(line, get_stats_text_style(&asset_server))
+                                .with_alignment(TextAlignment::Center),
+                            ..default()
+                        });
+                    }
+                });
+            // Retry button
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: BUTTON_STYLE,
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    RetryButton,
+                ))
+                .with_children(|parent: &mut ChildBuilder| {
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Retry", get_button_text_style(&asset_server))
+                            .with_alignment(TextAlignment::Center),
+                        ..Default::default()
+                    });
+                });
+            // Main menu button
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: BUTTON_STYLE,
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    MainMenuButton,
+                ))
+                .with_children(|parent: &mut ChildBuilder| {
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Main Menu", get_button_text_style(&asset_server))
+                            .with_alignment(TextAlignment::Center),
+                        ..Default::default()
+                    });
+                });
+        })
+        .id()
+}
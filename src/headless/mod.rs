@@ -0,0 +1,102 @@
+use std::process::ExitCode;
+use std::time::Duration;
+
+use bevy::input::InputPlugin;
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::ExitCondition;
+
+use resources::*;
+use systems::*;
+use crate::AppState;
+use crate::game::{GamePlugin, SIMULATION_STEP};
+use crate::game::player_commands::replay::Replay;
+use crate::game::player_commands::resources::ReplayPlayback;
+use crate::game::resources::CurrentLevel;
+
+mod systems;
+pub mod resources;
+
+/**
+ * Plays a level without a window nor a GPU, then prints a report : used to balance levels in batch.
+ * Every frame runs exactly one simulation step, as fast as the machine allows. Fails if the replay can't be loaded.
+ */
+pub fn run(args: &[String]) -> ExitCode {
+    let mut options = HeadlessOptions::from_args(args);
+    let replay = match options.replay.as_ref().map(Replay::load) {
+        Some(Ok(replay)) => Some(replay),
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+        None => None,
+    };
+    let level = match replay.as_ref() {
+        Some(replay) => {
+            options.map = replay.map.clone();
+            options.waves = replay.waves.clone();
+            options.build = None;
+            replay.level()
+        }
+        None => CurrentLevel {
+            map: options.map.clone(),
+            waves: options.waves.clone(),
+            ..default()
+        },
+    };
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins);
+    app
+        .add_plugin(LogPlugin::default())
+        .add_plugin(TransformPlugin)
+        .add_plugin(HierarchyPlugin)
+        .add_plugin(InputPlugin)
+        .add_plugin(WindowPlugin {
+            primary_window: None,
+            exit_condition: ExitCondition::DontExit,
+            close_when_requested: false,
+        })
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(ImagePlugin::default())
+    ;
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(SIMULATION_STEP)));
+
+    app.add_state::<AppState>();
+    app.insert_resource(level);
+    app.insert_resource(options);
+    if let Some(replay) = replay {
+        app.insert_resource(ReplayPlayback::new(replay));
+    }
+
+    app.add_plugin(GamePlugin);
+    app.add_plugin(HeadlessPlugin);
+
+    app.run();
+    ExitCode::SUCCESS
+}
+
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_asset::<BuildScript>()
+            .add_asset_loader(BuildScriptLoader)
+        ;
+        app.init_resource::<SimulationReport>();
+        app.add_startup_system(start_headless_game);
+        app.add_systems(
+            (
+                run_build_script,
+                record_leaks,
+                record_gold_curve,
+                stop_after_max_ticks,
+            ).in_set(OnUpdate(AppState::Game))
+        );
+        // towers are despawned on exit, the report is written before that happens
+        app.add_system(finish_headless_game.in_schedule(OnExit(AppState::Game)));
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use bevy::{
+    asset::{AssetLoader, LoadedAsset},
+    log,
+    prelude::*,
+    reflect::TypeUuid,
+};
+use serde::Deserialize;
+
+use crate::game::resources::TowerType;
+
+/// Simulation steps after which a run is stopped, e.g. when a wave can never be cleared.
+pub const DEFAULT_MAX_TICKS: u64 = 60 * 60 * 30;
+
+/// Simulation steps between two samples of the gold curve.
+pub const GOLD_SAMPLE_INTERVAL: u64 = 60 * 10;
+
+/**
+ * Command line of a headless run :
+ * `--headless [--map <tmx>] [--waves <waves.ron>] [--build <build.ron>] [--replay <replay.ron>] [--max-ticks <n>] [--report <file>]`.
+ * Asset paths are relative to the assets folder, the replay path to the working directory.
+ */
+#[derive(Resource, Clone)]
+pub struct HeadlessOptions {
+    pub map: String,
+    pub waves: String,
+    pub build: Option<String>,
+    /// Replay to play instead of the build script, its level replaces `map` and `waves`.
+    pub replay: Option<String>,
+    pub max_ticks: u64,
+    /// File the report is also written to.
+    pub report: Option<String>,
+}
+
+impl Default for HeadlessOptions {
+    fn default() -> Self {
+        Self {
+            map: "tiled/map_1.tmx".to_string(),
+            waves: "waves/map_1.waves.ron".to_string(),
+            build: None,
+            replay: None,
+            max_ticks: DEFAULT_MAX_TICKS,
+            report: None,
+        }
+    }
+}
+
+impl HeadlessOptions {
+    pub fn from_args(args: &[String]) -> Self {
+        let mut options = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--map" => if let Some(value) = args.next() {
+                    options.map = value.clone();
+                },
+                "--waves" => if let Some(value) = args.next() {
+                    options.waves = value.clone();
+                },
+                "--build" => if let Some(value) = args.next() {
+                    options.build = Some(value.clone());
+                },
+                "--replay" => if let Some(value) = args.next() {
+                    options.replay = Some(value.clone());
+                },
+                "--max-ticks" => if let Some(value) = args.next() {
+                    match value.parse() {
+                        Ok(max_ticks) => options.max_ticks = max_ticks,
+                        Err(_) => log::warn!("Ignored invalid --max-ticks {}.", value),
+                    }
+                },
+                "--report" => if let Some(value) = args.next() {
+                    options.report = Some(value.clone());
+                },
+                _ => {}
+            }
+        }
+        options
+    }
+}
+
+/// Towers to place during a headless run, loaded from a `*.build.ron` file.
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "6f0b5d0e-4a3c-4f38-9d57-3c1d2b8e7a41"]
+pub struct BuildScript {
+    /// Orders are placed one after another, each one waits until it can be afforded.
+    pub orders: Vec<BuildOrder>,
+}
+
+#[derive(Deserialize)]
+pub struct BuildOrder {
+    pub tower_type: TowerType,
+    /// Tile coordinates, (0, 0) being the bottom left tile.
+    pub tile: (u32, u32),
+    /// Index of the wave before which the tower is built, 0 for right at the start.
+    #[serde(default)]
+    pub wave: usize,
+}
+
+pub struct BuildScriptLoader;
+
+impl AssetLoader for BuildScriptLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut bevy::asset::LoadContext,
+    ) -> bevy::asset::BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let script: BuildScript = ron::de::from_bytes(bytes)
+                .map_err(|e| anyhow::anyhow!("Could not load build script: {e}"))?;
+
+            log::info!("Loaded {} build orders: {}", script.orders.len(), load_context.path().display());
+
+            load_context.set_default_asset(LoadedAsset::new(script));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["build.ron"];
+        EXTENSIONS
+    }
+}
+
+#[derive(Resource)]
+pub struct BuildScriptState {
+    pub script: Option<Handle<BuildScript>>,
+    /// Index of the next order to place.
+    pub next_order: usize,
+}
+
+pub struct GoldSample {
+    pub tick: u64,
+    pub wave: usize,
+    pub gold: u32,
+}
+
+/// Everything recorded during a headless run, printed at the end.
+#[derive(Resource, Default)]
+pub struct SimulationReport {
+    /// Number of leaked enemies and lives lost, per wave index.
+    pub leaks: BTreeMap<usize, (u32, u32)>,
+    pub gold_curve: Vec<GoldSample>,
+    /// The run was stopped by `HeadlessOptions::max_ticks` before the game was over.
+    pub timed_out: bool,
+}
@@ -0,0 +1,202 @@
+use std::fmt::Write;
+
+use bevy::app::AppExit;
+use bevy::log;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::AppState;
+use crate::game::SIMULATION_STEP;
+use crate::game::creep::events::{EnemyLeakedEvent, WaveClearedEvent, WaveStartedEvent};
+use crate::game::creep::resources::WaveState;
+use crate::game::player_commands::commands::PlayerCommand;
+use crate::game::player_commands::resources::PendingCommands;
+use crate::game::resources::{GameStats, Gold, PlayerLives, SimulationClock};
+use crate::game::tower::components::{Tower, TowerLevel, TowerStats};
+use crate::game::tower::resources::TowerCatalog;
+
+use super::resources::*;
+
+pub fn start_headless_game(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    options: Res<HeadlessOptions>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    commands.insert_resource(BuildScriptState {
+        script: options.build.as_ref().map(|build| asset_server.load(build.as_str())),
+        next_order: 0,
+    });
+    next_app_state.set(AppState::Game);
+}
+
+/**
+ * Places the scripted towers in order, as soon as their wave is reached and they can be afforded.
+ */
+pub fn run_build_script(
+    mut build_script_state: ResMut<BuildScriptState>,
+    build_scripts: Res<Assets<BuildScript>>,
+    wave_state: Option<Res<WaveState>>,
+    gold: Res<Gold>,
+    tower_catalog: Res<TowerCatalog>,
+    tilemap_query: Query<(), With<TileStorage>>,
+    mut pending_commands: ResMut<PendingCommands>,
+) {
+    let Some(script) = build_script_state.script.as_ref().and_then(|script| build_scripts.get(script)) else {
+        return;
+    };
+    let Some(wave_state) = wave_state else {
+        return;
+    };
+    if tilemap_query.is_empty() {
+        // towers can only be built once the map is spawned
+        return;
+    }
+
+    let mut available_gold = gold.current;
+    let mut next_order = build_script_state.next_order;
+    while let Some(order) = script.orders.get(next_order) {
+        let cost = tower_catalog.build_cost(order.tower_type);
+        if order.wave > wave_state.wave_index || cost > available_gold {
+            break;
+        }
+        pending_commands.push(PlayerCommand::BuildTower {
+            tower_type: order.tower_type,
+            tile: order.tile,
+        });
+        available_gold -= cost;
+        next_order += 1;
+    }
+    if next_order != build_script_state.next_order {
+        build_script_state.next_order = next_order;
+    }
+}
+
+pub fn record_leaks(
+    mut enemy_leaked_event_reader: EventReader<EnemyLeakedEvent>,
+    wave_state: Option<Res<WaveState>>,
+    mut report: ResMut<SimulationReport>,
+) {
+    let wave = wave_state.map_or(0, |wave_state| wave_state.wave_index);
+    for event in enemy_leaked_event_reader.iter() {
+        let (count, lives_lost) = report.leaks.entry(wave).or_default();
+        *count += 1;
+        *lives_lost += event.cost;
+    }
+}
+
+/**
+ * Samples the gold at the start and the end of every wave, and at a regular interval in between.
+ */
+pub fn record_gold_curve(
+    mut wave_started_event_reader: EventReader<WaveStartedEvent>,
+    mut wave_cleared_event_reader: EventReader<WaveClearedEvent>,
+    simulation_clock: Res<SimulationClock>,
+    wave_state: Option<Res<WaveState>>,
+    gold: Res<Gold>,
+    mut report: ResMut<SimulationReport>,
+) {
+    let wave_event = wave_started_event_reader.iter().map(|event| event.index)
+        .chain(wave_cleared_event_reader.iter().map(|event| event.index))
+        .last();
+    let last_sample_tick = report.gold_curve.last().map(|sample| sample.tick);
+    let interval_elapsed = last_sample_tick.is_none_or(|tick| simulation_clock.tick >= tick + GOLD_SAMPLE_INTERVAL);
+    if wave_event.is_none() && !interval_elapsed {
+        return;
+    }
+    if last_sample_tick == Some(simulation_clock.tick) {
+        return;
+    }
+    report.gold_curve.push(GoldSample {
+        tick: simulation_clock.tick,
+        wave: wave_event.or(wave_state.map(|wave_state| wave_state.wave_index)).unwrap_or(0),
+        gold: gold.current,
+    });
+}
+
+pub fn stop_after_max_ticks(
+    simulation_clock: Res<SimulationClock>,
+    options: Res<HeadlessOptions>,
+    mut report: ResMut<SimulationReport>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if simulation_clock.tick < options.max_ticks || report.timed_out {
+        return;
+    }
+    log::warn!("Stopped the simulation after {} ticks.", simulation_clock.tick);
+    report.timed_out = true;
+    next_app_state.set(AppState::GameOver);
+}
+
+pub fn finish_headless_game(
+    tower_query: Query<(&Tower, &TowerLevel, &TowerStats)>,
+    game_stats: Res<GameStats>,
+    player_lives: Res<PlayerLives>,
+    gold: Res<Gold>,
+    simulation_clock: Res<SimulationClock>,
+    options: Res<HeadlessOptions>,
+    report: Res<SimulationReport>,
+    mut app_exit_event_writer: EventWriter<AppExit>,
+) {
+    let text = format_report(&tower_query, &game_stats, &player_lives, &gold, &simulation_clock, &options, &report);
+    println!("{}", text);
+    if let Some(path) = &options.report {
+        if let Err(e) = std::fs::write(path, &text) {
+            log::error!("Could not write the report to {}: {}", path, e);
+        }
+    }
+    app_exit_event_writer.send(AppExit);
+}
+
+fn format_report(
+    tower_query: &Query<(&Tower, &TowerLevel, &TowerStats)>,
+    game_stats: &GameStats,
+    player_lives: &PlayerLives,
+    gold: &Gold,
+    simulation_clock: &SimulationClock,
+    options: &HeadlessOptions,
+    report: &SimulationReport,
+) -> String {
+    let mut text = String::new();
+    let result = if report.timed_out {
+        "stopped"
+    } else if game_stats.victory {
+        "victory"
+    } else {
+        "defeat"
+    };
+    let seconds = simulation_clock.tick as f32 * SIMULATION_STEP;
+
+    // writing to a String can't fail
+    let _ = writeln!(text, "=== Simulation report ===");
+    let _ = writeln!(text, "Map: {}", options.map);
+    let _ = writeln!(text, "Waves: {}", options.waves);
+    let _ = writeln!(text, "Build: {}", options.build.as_deref().unwrap_or("none"));
+    let _ = writeln!(text, "Result: {} after {} ticks ({:.1}s)", result, simulation_clock.tick, seconds);
+    let _ = writeln!(text, "Waves survived: {}", game_stats.waves_survived);
+    let _ = writeln!(text, "Lives left: {}", player_lives.current);
+    let _ = writeln!(text, "Gold: {} left, {} earned", gold.current, game_stats.gold_earned);
+
+    let total_leaks: u32 = report.leaks.values().map(|(count, _)| count).sum();
+    let _ = writeln!(text, "Leaks: {}", total_leaks);
+    for (wave, (count, lives_lost)) in report.leaks.iter() {
+        let _ = writeln!(text, "  wave {}: {} leaked, {} lives lost", wave + 1, count, lives_lost);
+    }
+
+    let _ = writeln!(text, "Kills: {}", game_stats.enemies_killed);
+    let mut towers: Vec<_> = tower_query.iter().collect();
+    towers.sort_by_key(|(tower, _, _)| (tower.tile_pos.x, tower.tile_pos.y));
+    for (tower, tower_level, tower_stats) in towers {
+        let _ = writeln!(
+            text,
+            "  {:?} tower level {} at ({}, {}): {} kills, {} damage",
+            tower.tower_type, tower_level.0 + 1, tower.tile_pos.x, tower.tile_pos.y, tower_stats.kills, tower_stats.damage_dealt,
+        );
+    }
+
+    let _ = writeln!(text, "Gold curve:");
+    for sample in report.gold_curve.iter() {
+        let _ = writeln!(text, "  tick {} (wave {}): {} g", sample.tick, sample.wave + 1, sample.gold);
+    }
+    text
+}
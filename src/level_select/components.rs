@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct LevelSelectMenu;
+
+/// Starts the level at this index of the `LevelCatalog`.
+#[derive(Component)]
+pub struct LevelButton {
+    pub index: usize,
+}
+
+#[derive(Component)]
+pub struct BackButton;
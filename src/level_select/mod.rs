@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+
+use systems::*;
+
+mod components;
+mod systems;
+mod styles;
+
+pub struct LevelSelectPlugin;
+
+impl Plugin for LevelSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_startup_system(load_level_catalog)
+            .add_systems(
+                (
+                    spawn_level_select_menu,
+                    interact_with_level_buttons,
+                    interact_with_back_button,
+                ).in_set(OnUpdate(AppState::LevelSelect))
+            )
+            .add_system(despawn_level_select_menu.in_schedule(OnExit(AppState::LevelSelect)))
+        ;
+    }
+}
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+use crate::styles::get_text_style;
+
+pub const LEVEL_SELECT_MENU_STYLE: Style = Style {
+    flex_direction: FlexDirection::Column,
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+    gap: Size::new(Val::Px(16.0), Val::Px(16.0)),
+    ..Style::DEFAULT
+};
+
+pub const LEVEL_LIST_STYLE: Style = Style {
+    flex_direction: FlexDirection::Row,
+    justify_content: JustifyContent::Center,
+    gap: Size::new(Val::Px(16.0), Val::Px(16.0)),
+    ..Style::DEFAULT
+};
+
+pub const LEVEL_BUTTON_STYLE: Style = Style {
+    flex_direction: FlexDirection::Column,
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    padding: UiRect::all(Val::Px(12.0)),
+    gap: Size::new(Val::Px(4.0), Val::Px(4.0)),
+    ..Style::DEFAULT
+};
+
+pub const THUMBNAIL_STYLE: Style = Style {
+    size: Size::all(Val::Px(192.0)),
+    ..Style::DEFAULT
+};
+
+pub const BUTTON_STYLE: Style = Style {
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Px(200.0), Val::Px(80.0)),
+    ..Style::DEFAULT
+};
+
+pub fn get_level_info_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    get_text_style(asset_server, 20.0, Color::rgb(0.8, 0.8, 0.8))
+}
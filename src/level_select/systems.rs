@@ -0,0 +1,169 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::game::levels::{LEVEL_CATALOG_PATH, LevelCatalog, LevelCatalogHandle};
+
+use super::components::*;
+use super::styles::*;
+use crate::styles::*;
+
+pub fn load_level_catalog(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(LevelCatalogHandle(asset_server.load(LEVEL_CATALOG_PATH)));
+}
+
+/**
+ * The menu lists the levels of the catalogue, so it is only spawned once the catalogue is loaded.
+ */
+pub fn spawn_level_select_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    level_catalog_handle: Res<LevelCatalogHandle>,
+    level_catalogs: Res<Assets<LevelCatalog>>,
+    level_select_menu_query: Query<(), With<LevelSelectMenu>>,
+) {
+    if !level_select_menu_query.is_empty() {
+        return;
+    }
+    let Some(level_catalog) = level_catalogs.get(&level_catalog_handle.0) else {
+        return;
+    };
+    build_level_select_menu(&mut commands, &asset_server, level_catalog);
+}
+
+pub fn despawn_level_select_menu(mut commands: Commands, level_select_menu_query: Query<Entity, With<LevelSelectMenu>>) {
+    if let Ok(level_select_menu_entity) = level_select_menu_query.get_single() {
+        commands.entity(level_select_menu_entity).despawn_recursive();
+    }
+}
+
+pub fn interact_with_level_buttons(
+    mut commands: Commands,
+    mut button_query: Query<
+        (&Interaction, &LevelButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    level_catalog_handle: Res<LevelCatalogHandle>,
+    level_catalogs: Res<Assets<LevelCatalog>>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, level_button, mut background_color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                let Some(level) = level_catalogs.get(&level_catalog_handle.0)
+                    .and_then(|level_catalog| level_catalog.levels.get(level_button.index)) else {
+                    continue;
+                };
+                commands.insert_resource(level.level());
+                next_app_state.set(AppState::Game);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn interact_with_back_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<BackButton>),
+    >,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                next_app_state.set(AppState::MainMenu);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn build_level_select_menu(commands: &mut Commands, asset_server: &Res<AssetServer>, level_catalog: &LevelCatalog) -> Entity {
+    commands
+        .spawn((
+            NodeBundle {
+                style: LEVEL_SELECT_MENU_STYLE,
+                ..default()
+            },
+            LevelSelectMenu,
+            Name::new("LevelSelectMenu"),
+        ))
+        .with_children(|parent: &mut ChildBuilder| {
+            parent.spawn(TextBundle {
+                text: Text::from_section("Select a level", get_title_text_style(&asset_server, Color::WHITE))
+                    .with_alignment(TextAlignment::Center),
+                ..default()
+            });
+            // Levels
+            parent
+                .spawn(NodeBundle {
+                    style: LEVEL_LIST_STYLE,
+                    ..default()
+                })
+                .with_children(|parent: &mut ChildBuilder| {
+                    for (index, level) in level_catalog.levels.iter().enumerate() {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: LEVEL_BUTTON_STYLE,
+                                    background_color: NORMAL_BUTTON_COLOR.into(),
+                                    ..default()
+                                },
+                                LevelButton {
+                                    index,
+                                },
+                            ))
+                            .with_children(|parent: &mut ChildBuilder| {
+                                parent.spawn(ImageBundle {
+                                    style: THUMBNAIL_STYLE,
+                                    image: asset_server.load(level.thumbnail.as_str()).into(),
+                                    ..default()
+                                });
+                                parent.spawn(TextBundle {
+                                    text: Text::from_section(level.name.as_str(), get_button_text_style(&asset_server))
+                                        .with_alignment(TextAlignment::Center),
+                                    ..default()
+                                });
+                                parent.spawn(TextBundle {
+                                    text: Text::from_section(
+                                        format!("{} gold - {} lives", level.starting_gold, level.starting_lives),
+                                        get_level_info_text_style(&asset_server),
+                                    )
+                                        .with_alignment(TextAlignment::Center),
+                                    ..default()
+                                });
+                            });
+                    }
+                });
+            // Back button
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: BUTTON_STYLE,
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    BackButton,
+                ))
+                .with_children(|parent: &mut ChildBuilder| {
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Back", get_button_text_style(&asset_server))
+                            .with_alignment(TextAlignment::Center),
+                        ..default()
+                    });
+                });
+        })
+        .id()
+}
@@ -1,3 +1,4 @@
+use std::process::ExitCode;
 use std::thread::current;
 
 use bevy::input::ButtonState;
@@ -10,13 +11,30 @@ use bevy::sprite::{Anchor, ExtractedSprite, ExtractedSprites, SpriteSystem};
 use bevy::window::PrimaryWindow;
 use bevy_ecs_tilemap::prelude::*;
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
-use crate::game::GamePlugin;
+use crate::game::{GamePlugin, GameViewPlugin};
+use crate::game::player_commands::replay::Replay;
+use crate::game::player_commands::resources::ReplayPlayback;
+use crate::game_over::GameOverPlugin;
+use crate::level_select::LevelSelectPlugin;
 use crate::main_menu::MainMenuPlugin;
 
+mod check_map;
 mod game;
+mod game_over;
+mod headless;
+mod level_select;
 mod main_menu;
+mod styles;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().collect();
+    if args.iter().any(|arg| arg == "--check-map") {
+        return check_map::run(&args);
+    }
+    if args.iter().any(|arg| arg == "--headless") {
+        return headless::run(&args);
+    }
 
-fn main() {
     let mut app = App::new();
 
     app.insert_resource(ClearColor(Color::rgb(0.2, 0.2, 0.2)));
@@ -24,6 +42,18 @@ fn main() {
 
     app.add_state::<AppState>();
 
+    // `--replay <file>` watches a recorded game instead of showing the main menu
+    if let Some(path) = args.iter().position(|arg| arg == "--replay").and_then(|index| args.get(index + 1)) {
+        match Replay::load(path) {
+            Ok(replay) => {
+                app.insert_resource(replay.level());
+                app.insert_resource(ReplayPlayback::new(replay));
+                app.insert_resource(NextState(Some(AppState::Game)));
+            }
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+
     app.add_plugins(DefaultPlugins
         .set(WindowPlugin {
             primary_window: Some(Window {
@@ -35,21 +65,32 @@ fn main() {
             ..default()
         })
         .set(ImagePlugin::default_nearest())
+        // maps edited in Tiled are reloaded in the running game
+        .set(AssetPlugin {
+            watch_for_changes: cfg!(debug_assertions),
+            ..default()
+        })
     );
     app.add_plugin(WorldInspectorPlugin::new());
 
     app.add_plugin(GamePlugin);
+    app.add_plugin(GameViewPlugin);
     app.add_plugin(MainMenuPlugin);
+    app.add_plugin(LevelSelectPlugin);
+    app.add_plugin(GameOverPlugin);
 
     app.add_startup_system(setup_camera);
 
     app.run();
+
+    ExitCode::SUCCESS
 }
 
 #[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
 pub enum AppState {
     #[default]
     MainMenu,
+    LevelSelect,
     Game,
     GameOver,
 }
@@ -6,5 +6,8 @@ pub struct MainMenu;
 #[derive(Component)]
 pub struct PlayButton;
 
+#[derive(Component)]
+pub struct LoadButton;
+
 #[derive(Component)]
 pub struct QuitButton;
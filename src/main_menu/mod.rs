@@ -17,6 +17,7 @@ impl Plugin for MainMenuPlugin {
             .add_systems(
                 (
                     interact_with_play_button,
+                    interact_with_load_button,
                     interact_with_quit_button,
                 ).in_set(OnUpdate(AppState::MainMenu))
             )
@@ -1,9 +1,5 @@
 use bevy::prelude::*;
 
-pub const NORMAL_BUTTON_COLOR: Color = Color::rgb(0.15, 0.15, 0.15);
-pub const HOVERED_BUTTON_COLOR: Color = Color::rgb(0.25, 0.25, 0.25);
-pub const PRESSED_BUTTON_COLOR: Color = Color::rgb(0.35, 0.75, 0.35);
-
 pub const MAIN_MENU_STYLE: Style = Style {
     flex_direction: FlexDirection::Column,
     justify_content: JustifyContent::Center,
@@ -33,19 +29,3 @@ pub const TITLE_STYLE: Style = Style {
     size: Size::new(Val::Percent(300.0), Val::Px(120.0)),
     ..Style::DEFAULT
 };
-
-pub fn get_title_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
-    TextStyle {
-        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-        font_size: 64.0,
-        color: Color::WHITE,
-    }
-}
-
-pub fn get_button_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
-    TextStyle {
-        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-        font_size: 32.0,
-        color: Color::WHITE,
-    }
-}
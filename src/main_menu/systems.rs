@@ -1,9 +1,13 @@
 use bevy::app::AppExit;
+use bevy::log;
 use bevy::prelude::*;
 use crate::AppState;
+use crate::game::save::file::{SAVE_PATH, SaveFile};
+use crate::game::save::resources::PendingLoad;
 
 use super::components::*;
 use super::styles::*;
+use crate::styles::*;
 
 pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
     build_main_menu(&mut commands, &asset_server);
@@ -26,7 +30,41 @@ pub fn interact_with_play_button(
         match *interaction {
             Interaction::Clicked => {
                 *background_color = PRESSED_BUTTON_COLOR.into();
-                next_app_state.set(AppState::Game);
+                next_app_state.set(AppState::LevelSelect);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+/**
+ * Starts the game from the save slot, its level is loaded first then the save is restored on top of it.
+ */
+pub fn interact_with_load_button(
+    mut commands: Commands,
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<LoadButton>),
+    >,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                match SaveFile::load(SAVE_PATH) {
+                    Ok(save) => {
+                        commands.insert_resource(save.level());
+                        commands.insert_resource(PendingLoad(save));
+                        next_app_state.set(AppState::Game);
+                    }
+                    Err(e) => log::warn!("{e}"),
+                }
             }
             Interaction::Hovered => {
                 *background_color = HOVERED_BUTTON_COLOR.into();
@@ -86,7 +124,7 @@ pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>)
                     parent.spawn(TextBundle {
                         text: Text::from_section(
                             "Ioni Tower Defense",
-                            get_title_text_style(&asset_server),
+                            get_title_text_style(&asset_server, Color::WHITE),
                         )
                             .with_alignment(TextAlignment::Center),
                         ..default()
@@ -114,6 +152,23 @@ pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>)
                         ..Default::default()
                     });
                 });
+            // Load button
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: BUTTON_STYLE,
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    LoadButton,
+                ))
+                .with_children(|parent: &mut ChildBuilder| {
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Load", get_button_text_style(&asset_server))
+                            .with_alignment(TextAlignment::Center),
+                        ..Default::default()
+                    });
+                });
             // Quit button
             parent
                 .spawn((
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+pub const NORMAL_BUTTON_COLOR: Color = Color::rgb(0.15, 0.15, 0.15);
+pub const HOVERED_BUTTON_COLOR: Color = Color::rgb(0.25, 0.25, 0.25);
+pub const PRESSED_BUTTON_COLOR: Color = Color::rgb(0.35, 0.75, 0.35);
+
+/// Font of every text of the menus and the game UI.
+pub const FONT_PATH: &str = "fonts/FiraSans-Bold.ttf";
+
+pub fn get_text_style(asset_server: &Res<AssetServer>, font_size: f32, color: Color) -> TextStyle {
+    TextStyle {
+        font: asset_server.load(FONT_PATH),
+        font_size,
+        color,
+    }
+}
+
+pub fn get_title_text_style(asset_server: &Res<AssetServer>, color: Color) -> TextStyle {
+    get_text_style(asset_server, 64.0, color)
+}
+
+pub fn get_button_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    get_text_style(asset_server, 32.0, Color::WHITE)
+}